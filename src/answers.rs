@@ -0,0 +1,120 @@
+use crate::solution::Answer;
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+use toml::Value;
+
+// Known good answers for the real inputs, keyed by (day, part).
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u32, u32), Answer>);
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: Answer, actual: Answer },
+    Unknown(Answer),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "ok"),
+            Verdict::Wrong { expected, actual } => write!(f, "MISMATCH, expected {expected:?} got {actual:?}"),
+            Verdict::Unknown(Answer::Unsolved) => write!(f, "not solved"),
+            Verdict::Unknown(actual) => write!(f, "no recorded answer, got {actual:?}"),
+        }
+    }
+}
+
+pub fn to_answer(value: &Value) -> Option<Answer> {
+    match value {
+        Value::Integer(n) => Some(Answer::Int(*n)),
+        Value::String(s) if s.contains('\n') => Some(Answer::Bitmap(s.lines().map(String::from).collect())),
+        Value::String(s) => Some(Answer::Text(s.clone())),
+        _ => None,
+    }
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table: BTreeMap<String, BTreeMap<String, Value>> = toml::from_str(text).map_err(|e| e.to_string())?;
+        let mut answers = BTreeMap::new();
+        for (day_key, parts) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or(format!("Expected [dayN], found [{day_key}]"))?;
+            for (part_key, value) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(format!("Expected part1 or part2 in [{day_key}], found {part_key}")),
+                };
+                let answer = to_answer(&value).ok_or(format!("{day_key}.{part_key} must be an integer or a string"))?;
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Self(answers))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&Answer> {
+        self.0.get(&(day, part))
+    }
+
+    pub fn check(&self, day: u32, part: u32, actual: Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if *expected == actual => Verdict::Correct,
+            Some(expected) => Verdict::Wrong { expected: expected.clone(), actual },
+            None => Verdict::Unknown(actual),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const ANSWERS: &str = indoc! {r#"
+    [day1]
+    part1 = 24000
+    part2 = 45000
+
+    [day5]
+    part1 = "CMZ"
+
+    [day10]
+    part2 = """
+    ##..
+    ..##"""
+    "#
+    };
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(Some(&Answer::Int(45000)), answers.get(1, 2));
+        assert_eq!(Some(&Answer::Text(String::from("CMZ"))), answers.get(5, 1));
+        assert_eq!(None, answers.get(5, 2));
+        assert_eq!(
+            Some(&Answer::Bitmap(vec![String::from("##.."), String::from("..##")])),
+            answers.get(10, 2)
+        );
+        assert!(Answers::parse("[dayX]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day1]\npart3 = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(Verdict::Correct, answers.check(1, 1, Answer::Int(24000)));
+        assert_eq!(
+            Verdict::Wrong { expected: Answer::Int(45000), actual: Answer::Int(1) },
+            answers.check(1, 2, Answer::Int(1))
+        );
+        assert_eq!(Verdict::Unknown(Answer::Unsolved), answers.check(16, 1, Answer::Unsolved));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{error::ParseError, solution::DynSolution};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty());
+        samples.sort();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Settings {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { warmup: 1, iterations: 10 }
+    }
+}
+
+#[derive(Debug)]
+pub struct Timings {
+    pub day: u32,
+    pub iterations: usize,
+    // (stage, stats) with the stages "parse", "part1" and "part2"
+    pub stages: Vec<(&'static str, Stats)>,
+}
+
+pub fn measure<R>(settings: &Settings, mut f: impl FnMut() -> R) -> Stats {
+    for _ in 0..settings.warmup {
+        black_box(f());
+    }
+    let samples = (0..settings.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+pub fn bench(s: &dyn DynSolution, indata: &str, settings: &Settings) -> Result<Timings, ParseError> {
+    let input = s.parse_any(indata)?;
+    let mut stages = vec![("parse", measure(settings, || s.parse_any(indata)))];
+    for (stage, part) in [("part1", 1), ("part2", 2)] {
+        stages.push((stage, measure(settings, || s.part_any(input.as_ref(), part))));
+    }
+    Ok(Timings { day: s.day(), iterations: settings.iterations.max(1), stages })
+}
+
+fn format_duration(d: Duration) -> String {
+    match d.as_nanos() {
+        n if n < 1_000 => format!("{n}ns"),
+        n if n < 1_000_000 => format!("{:.1}µs", n as f64 / 1e3),
+        n if n < 1_000_000_000 => format!("{:.1}ms", n as f64 / 1e6),
+        n => format!("{:.2}s", n as f64 / 1e9),
+    }
+}
+
+pub fn text_table(timings: &[Timings]) -> Vec<String> {
+    let mut res = vec![format!("{:>4} {:<6} {:>10} {:>10} {:>10}", "day", "stage", "min", "median", "max")];
+    for t in timings {
+        for (stage, stats) in &t.stages {
+            res.push(format!(
+                "{:>4} {:<6} {:>10} {:>10} {:>10}",
+                t.day,
+                stage,
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.max)
+            ));
+        }
+    }
+    res
+}
+
+pub fn csv_table(timings: &[Timings]) -> Vec<String> {
+    let mut res = vec![String::from("day,stage,iterations,min_ns,median_ns,max_ns")];
+    for t in timings {
+        for (stage, stats) in &t.stages {
+            res.push(format!(
+                "{},{},{},{},{},{}",
+                t.day,
+                stage,
+                t.iterations,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.max.as_nanos()
+            ));
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day1::Day1;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3), ms(9), ms(2)]);
+        assert_eq!(Stats { min: ms(1), median: ms(3), max: ms(9) }, stats);
+    }
+
+    #[test]
+    fn test_bench() {
+        let mut calls = 0;
+        measure(&Settings { warmup: 2, iterations: 3 }, || calls += 1);
+        assert_eq!(5, calls);
+
+        let timings = bench(&Day1::default(), "1\n2\n\n3\n\n4\n", &Settings { warmup: 0, iterations: 2 }).unwrap();
+        assert_eq!(vec!["parse", "part1", "part2"], timings.stages.iter().map(|s| s.0).collect::<Vec<_>>());
+        let csv = csv_table(&[timings]);
+        assert_eq!(4, csv.len());
+        assert!(csv[1].starts_with("1,parse,2,"));
+    }
+}
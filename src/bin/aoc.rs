@@ -0,0 +1,570 @@
+use advent_of_code_2022::{
+    answers::{Answers, Verdict},
+    day12, day14, day15, day2, day8, day9, day10,
+    bench::{self, Settings},
+    client::{Client, Feedback},
+    difftest::{self, Comparison},
+    examples,
+    generate::{generate, DEFAULT_SIZE},
+    image::{self, Frames},
+    input, interpret, inventory,
+    output::{format_records, Format, Record},
+    registry::{solver, solvers},
+    scaffold,
+    solution::{Answer, DynSolution},
+    teams::{self, Method},
+    visualize::Scene,
+    watch::{self, Watched},
+};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Instant,
+};
+
+const USAGE: &str = "Usage:
+    aoc run <day> [--part <1|2>] [--input <path|->] [--format <text|json|csv>] [--<option> <value> ...]
+    aoc run --all [--part <1|2>] [--format <text|json|csv>]
+    aoc verify [<day> ...] [--answers <path>]
+    aoc bench [<day> ...] [--warmup <n>] [--iterations <n>] [--format <text|csv>]
+    aoc generate <day> [--seed <n>] [--size <n>]
+    aoc difftest [<day> ...] [--seeds <n>] [--size <n>]
+    aoc extract <day> <page.html> [--output <path>] [--force]
+    aoc fetch <day>
+    aoc submit <day> <1|2> [<answer>]
+    aoc new <day>
+    aoc watch <day> [--release]
+    aoc image <day> <path.png|ppm|pgm> [--scale <n>] [--size <n>] [--input <path>] [--<option> <value> ...]
+    aoc inventory [--input <path|->] [--format <text|json|csv>] [--bins <n>]
+    aoc teams <k> [--method <auto|exact|greedy|kk>] [--input <path|->] [--format <text|json|csv>]
+    aoc interpret [--game <name|path>] [--top <n>] [--input <path|->] [--format <text|json|csv>]
+    aoc frames <day> <dir> [--format <png|ppm|pgm>] [--steps <n>] [--size <w>x<h>] [--scale <n>] [--input <path>]
+
+The default input is data/dayN.txt, the recorded answers are in data/answers.toml and
+the examples extracted from puzzle pages in data/examples/dayN.toml, set AOC_DATA_DIR
+to read them from another directory. image draws days 8, 12, 14 and 15 and frames
+records the animations of days 9, 10, 12 and 14. inventory gives statistics of the day 1
+calorie inventory and teams splits its elves into k teams with totals as equal as
+possible, exactly for up to 16 elves. interpret ranks every reading of the second column
+of the day 2 strategy guide, as moves or as outcomes. fetch and submit need the session
+cookie of adventofcode.com in AOC_SESSION, the feedback on answers is kept in
+data/submissions.txt.";
+
+#[derive(Default)]
+struct RunArgs {
+    day: Option<u32>,
+    all: bool,
+    part: Option<u32>,
+    input: Option<String>,
+    format: Format,
+    options: Vec<(String, String)>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut res = RunArgs::default();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--all" => res.all = true,
+            "--part" => {
+                let part = it.next().ok_or("--part needs a value")?;
+                res.part = Some(part.parse().map_err(|_| format!("Invalid part: {part}"))?);
+            }
+            "--input" => res.input = Some(it.next().ok_or("--input needs a value")?.clone()),
+            "--format" => res.format = it.next().ok_or("--format needs a value")?.parse()?,
+            option if option.starts_with("--") => {
+                let value = it.next().ok_or(format!("{option} needs a value"))?;
+                res.options.push((option[2..].to_string(), value.clone()));
+            }
+            day => res.day = Some(day.parse().map_err(|_| format!("Invalid day: {day}"))?),
+        }
+    }
+    match (res.all, res.day) {
+        (true, Some(_)) => Err(String::from("Give either a day or --all")),
+        (true, None) if res.input.is_some() || !res.options.is_empty() => {
+            Err(String::from("--input and options can not be combined with --all"))
+        }
+        (false, None) => Err(String::from("No day given")),
+        _ => Ok(res),
+    }
+}
+
+fn run_solver(s: &dyn DynSolution, parts: &[u32], input: Option<&str>) -> Result<Vec<Record>, String> {
+    let indata = input::read_input(s.day(), input)?;
+    let input = s.parse_any(&indata).map_err(|e| e.to_string())?;
+    let mut records = vec![];
+    for &part in parts {
+        let start = Instant::now();
+        let answer = s.part_any(input.as_ref(), part);
+        records.push(Record { day: s.day(), part, answer, elapsed: start.elapsed() });
+    }
+    Ok(records)
+}
+
+fn print_records(format: Format, records: &[Record]) {
+    for line in format_records(format, records) {
+        println!("{line}");
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let args = parse_run_args(args)?;
+    let parts = match args.part {
+        Some(p @ (1 | 2)) => vec![p],
+        Some(p) => return Err(format!("Invalid part: {p}")),
+        None => vec![1, 2],
+    };
+    if args.all {
+        // Text is printed day by day as it is solved, the other formats as one document.
+        // A day that fails is reported on stderr and the others still run.
+        let (mut records, mut failures) = (vec![], 0);
+        for s in solvers() {
+            let solved = match run_solver(s.as_ref(), &parts, None) {
+                Ok(solved) => solved,
+                Err(e) => {
+                    eprintln!("Day {}: {e}", s.day());
+                    failures += 1;
+                    continue;
+                }
+            };
+            match args.format {
+                Format::Text => print_records(args.format, &solved),
+                _ => records.extend(solved),
+            }
+        }
+        print_records(args.format, &records);
+        match failures {
+            0 => Ok(()),
+            n => Err(format!("{n} days could not be run")),
+        }
+    } else {
+        let day = args.day.unwrap();
+        let mut s = solver(day).ok_or(format!("Day {day} is not implemented"))?;
+        for (name, value) in &args.options {
+            s.set_option(name, value)?;
+        }
+        print_records(args.format, &run_solver(s.as_ref(), &parts, args.input.as_deref())?);
+        Ok(())
+    }
+}
+
+fn verify_solver(s: &dyn DynSolution, answers: &Answers) -> Result<usize, String> {
+    let indata = input::read_input(s.day(), None)?;
+    let input = s.parse_any(&indata).map_err(|e| e.to_string())?;
+    let mut failures = 0;
+    for part in 1..=2 {
+        let verdict = answers.check(s.day(), part, s.part_any(input.as_ref(), part));
+        if let Verdict::Wrong { .. } = verdict {
+            failures += 1;
+        }
+        println!("Day {} part {part}: {verdict}", s.day());
+    }
+    Ok(failures)
+}
+
+fn verify(args: &[String]) -> Result<(), String> {
+    let mut path = input::data_dir().join("answers.toml");
+    let mut days = vec![];
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--answers" => path = PathBuf::from(it.next().ok_or("--answers needs a value")?),
+            day => days.push(day.parse::<u32>().map_err(|_| format!("Invalid day: {day}"))?),
+        }
+    }
+    let answers = Answers::load(&path)?;
+    let mut failures = 0;
+    for s in solvers().iter().filter(|s| days.is_empty() || days.contains(&s.day())) {
+        failures += verify_solver(s.as_ref(), &answers).unwrap_or_else(|e| {
+            println!("Day {}: {e}", s.day());
+            1
+        });
+    }
+    match failures {
+        0 => Ok(()),
+        n => Err(format!("{n} answers did not verify")),
+    }
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let mut settings = Settings::default();
+    let mut csv = false;
+    let mut days = vec![];
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--warmup" => settings.warmup = number(arg, it.next())?,
+            "--iterations" => settings.iterations = number(arg, it.next())?,
+            "--format" => match it.next().map(|s| s.as_str()) {
+                Some("text") => csv = false,
+                Some("csv") => csv = true,
+                _ => return Err(String::from("--format must be text or csv")),
+            },
+            day => days.push(day.parse::<u32>().map_err(|_| format!("Invalid day: {day}"))?),
+        }
+    }
+    let mut timings = vec![];
+    for s in solvers().iter().filter(|s| days.is_empty() || days.contains(&s.day())) {
+        let indata = input::read_input(s.day(), None)?;
+        timings.push(bench::bench(s.as_ref(), &indata, &settings).map_err(|e| e.to_string())?);
+    }
+    let table = if csv { bench::csv_table(&timings) } else { bench::text_table(&timings) };
+    for line in table {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn generate_input(args: &[String]) -> Result<(), String> {
+    let (mut day, mut seed, mut size) = (None, 0, DEFAULT_SIZE);
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--seed" => seed = number(arg, it.next())?,
+            "--size" => size = number(arg, it.next())?,
+            d => day = Some(d.parse::<u32>().map_err(|_| format!("Invalid day: {d}"))?),
+        }
+    }
+    let day = day.ok_or("No day given")?;
+    print!("{}", generate(day, seed, size).ok_or(format!("There is no generator for day {day}"))?);
+    Ok(())
+}
+
+// Compares every day with its reference solver on generated inputs, on seeds 0..n.
+fn difftest(args: &[String]) -> Result<(), String> {
+    let (mut seeds, mut size) = (20, 20);
+    let mut days = vec![];
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--seeds" => seeds = number(arg, it.next())?,
+            "--size" => size = number(arg, it.next())?,
+            day => days.push(day.parse::<u32>().map_err(|_| format!("Invalid day: {day}"))?),
+        }
+    }
+    // Panics are reported as mismatches, not as they happen.
+    std::panic::set_hook(Box::new(|_| {}));
+    let mut mismatches = 0;
+    for s in solvers().iter().filter(|s| days.is_empty() || days.contains(&s.day())) {
+        match difftest::check(s.day(), 0..seeds, size) {
+            Comparison::Mismatch(mismatch) => {
+                println!("{mismatch}");
+                mismatches += 1;
+            }
+            Comparison::Skipped(reason) => println!("Day {}: skipped, {reason}", s.day()),
+            Comparison::Agrees { unsolved: 0 } => println!("Day {}: agrees on {seeds} inputs", s.day()),
+            Comparison::Agrees { unsolved } => {
+                println!("Day {}: agrees on {seeds} inputs, leaving out {unsolved} unsolved parts", s.day())
+            }
+        }
+    }
+    match mismatches {
+        0 => Ok(()),
+        n => Err(format!("{n} days disagree with their reference solver")),
+    }
+}
+
+// Writes the examples of a saved puzzle page as a fixture that the tests run.
+fn extract(args: &[String]) -> Result<(), String> {
+    let (mut output, mut force) = (None, false);
+    let mut positional = vec![];
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--output" => output = Some(PathBuf::from(it.next().ok_or("--output needs a value")?)),
+            "--force" => force = true,
+            _ => positional.push(arg),
+        }
+    }
+    let [day, page] = positional[..] else {
+        return Err(String::from("Usage: aoc extract <day> <page.html> [--output <path>] [--force]"));
+    };
+    let day: u32 = day.parse().map_err(|_| format!("Invalid day: {day}"))?;
+    let path = output.unwrap_or_else(|| examples::path(&input::data_dir().join("examples"), day));
+    if path.exists() && !force {
+        return Err(format!("{} already exists, use --force to replace it", path.display()));
+    }
+    let html = fs::read_to_string(page).map_err(|e| format!("{page}: {e}"))?;
+    let found = examples::extract(&html).map_err(|e| format!("{page}: {e}"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    }
+    fs::write(&path, examples::to_toml(&found)).map_err(|e| format!("{}: {e}", path.display()))?;
+    for (ix, example) in found.iter().enumerate() {
+        let answer = |part| example.answer(part).map_or(String::from("none"), |a| a.to_string());
+        println!("Example {}: {} lines, part 1 {}, part 2 {}", ix + 1, example.input.lines().count(), answer(1), answer(2));
+    }
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+fn fetch(args: &[String]) -> Result<(), String> {
+    let [day] = args else {
+        return Err(String::from("Usage: aoc fetch <day>"));
+    };
+    let day: u32 = day.parse().map_err(|_| format!("Invalid day: {day}"))?;
+    let client = Client::from_env(&input::data_dir())?;
+    let (indata, downloaded) = client.fetch(day)?;
+    let path = client.input_path(day);
+    match downloaded {
+        true => println!("Wrote {} lines to {}", indata.lines().count(), path.display()),
+        false => println!("{} is already there", path.display()),
+    }
+    Ok(())
+}
+
+// Submits the given answer, or what the solver finds for the day's input.
+fn submit(args: &[String]) -> Result<(), String> {
+    let (day, part, answer) = match args {
+        [day, part] => (day, part, None),
+        [day, part, answer] => (day, part, Some(answer.clone())),
+        _ => return Err(String::from("Usage: aoc submit <day> <1|2> [<answer>]")),
+    };
+    let day: u32 = day.parse().map_err(|_| format!("Invalid day: {day}"))?;
+    let part = match part.as_str() {
+        "1" => 1,
+        "2" => 2,
+        _ => return Err(format!("Invalid part: {part}")),
+    };
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let s = solver(day).ok_or(format!("Day {day} is not solved yet"))?;
+            match s.solve(&input::read_input(day, None)?, part).map_err(|e| e.to_string())? {
+                Answer::Int(n) => n.to_string(),
+                Answer::Text(text) => text,
+                Answer::Bitmap(_) => return Err(String::from("Read the letters from 'aoc run' and give them as the answer")),
+                Answer::Unsolved => return Err(format!("Day {day} part {part} is not solved yet")),
+            }
+        }
+    };
+    let client = Client::from_env(&input::data_dir())?;
+    let feedback = client.submit(day, part, &answer)?;
+    println!("Day {day} part {part}: {answer}: {feedback}");
+    match feedback {
+        Feedback::Correct => Ok(()),
+        _ => Err(String::from("The answer was not accepted")),
+    }
+}
+
+// Run from the root of the crate, as it adds to the sources.
+fn new_day(args: &[String]) -> Result<(), String> {
+    let [day] = args else {
+        return Err(String::from("Usage: aoc new <day>"));
+    };
+    let day: u32 = day.parse().map_err(|_| format!("Invalid day: {day}"))?;
+    let root = env::current_dir().map_err(|e| e.to_string())?;
+    if !root.join("Cargo.toml").exists() {
+        return Err(String::from("Run aoc new from the directory with Cargo.toml"));
+    }
+    for path in scaffold::create(&root, &input::data_dir(), day)? {
+        println!("Wrote {}", path.strip_prefix(&root).unwrap_or(&path).display());
+    }
+    Ok(())
+}
+
+fn watch_day(args: &[String]) -> Result<(), String> {
+    let (day, release) = match args {
+        [day] => (day, false),
+        [day, flag] if flag == "--release" => (day, true),
+        _ => return Err(String::from("Usage: aoc watch <day> [--release]")),
+    };
+    let day: u32 = day.parse().map_err(|_| format!("Invalid day: {day}"))?;
+    let root = env::current_dir().map_err(|e| e.to_string())?;
+    if !root.join("Cargo.toml").exists() {
+        return Err(String::from("Run aoc watch from the directory with Cargo.toml"));
+    }
+    let data_dir = root.join(input::data_dir());
+    let answers = Answers::load(&data_dir.join("answers.toml")).unwrap_or_default();
+    let mut watcher = watch::watcher(Watched::new(&root, &data_dir, day))?;
+    let mut previous = None;
+    loop {
+        let report = watch::run_round(&root, day, release)?;
+        for line in watch::summary(day, &report, previous.as_ref(), &answers) {
+            println!("{line}");
+        }
+        if report.build_errors.is_none() {
+            previous = Some(report);
+        }
+        println!("Watching day {day}, Ctrl-C to stop");
+        let changed = watcher.wait()?;
+        let names: Vec<String> = changed.iter().map(|p| p.strip_prefix(&root).unwrap_or(p).display().to_string()).collect();
+        println!("\nChanged: {}", names.join(", "));
+    }
+}
+
+fn number<T: FromStr>(name: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or(format!("{name} needs a value"))?;
+    value.parse().map_err(|_| format!("Invalid value for {name}: {value}"))
+}
+
+fn image(args: &[String]) -> Result<(), String> {
+    let (mut scale, mut size, mut input) = (None, 400, None);
+    let (mut positional, mut options) = (vec![], vec![]);
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--scale" => scale = Some(number(arg, it.next())?),
+            "--size" => size = number(arg, it.next())?,
+            "--input" => input = Some(it.next().ok_or("--input needs a value")?.as_str()),
+            flag if flag.starts_with("--") => options.push((&flag[2..], it.next().ok_or(format!("{flag} needs a value"))?.as_str())),
+            _ => positional.push(arg),
+        }
+    }
+    let [day, path] = positional[..] else {
+        return Err(String::from("Usage: aoc image <day> <path.png|ppm|pgm> [--scale <n>] [--size <n>] [--input <path>] [--<option> <value> ...]"));
+    };
+    let day: u32 = day.parse().map_err(|_| format!("Invalid day: {day}"))?;
+    let mut solver = solver(day).ok_or(format!("There is no solver for day {day}"))?;
+    input::set_options(solver.as_mut(), &options)?;
+    let indata = input::read_input(day, input)?;
+    let picture = match day {
+        8 => day8::picture(&day8::parse_indata(&indata).map_err(|e| e.to_string())?),
+        12 => {
+            let (grid, start, end) = day12::parse_maze(&indata).map_err(|e| e.to_string())?;
+            let path = day12::solve_maze(&grid, &start, &end).map(|p| p.nodes).unwrap_or_default();
+            day12::picture(&grid, &path)
+        }
+        14 => {
+            let mut cave = day14::Cave::from(&day14::parse_indata(&indata).map_err(|e| e.to_string())?);
+            day14::count_sand(&mut cave);
+            day14::picture(&cave)
+        }
+        15 => {
+            let (_, max_coord) = solver.options().into_iter().find(|(name, _)| *name == "max-coord").unwrap();
+            let ez = day15::parse_indata(&indata).map_err(|e| e.to_string())?;
+            day15::picture(&ez, max_coord.parse().unwrap(), size)
+        }
+        _ => return Err(format!("There is no picture of day {day}, only of days 8, 12, 14 and 15")),
+    };
+    // The day 15 picture is already scaled down to `size`.
+    let picture = image::scale(&picture, scale.unwrap_or(if day == 15 { 1 } else { 4 }));
+    image::save(&picture, Path::new(path))?;
+    println!("Wrote {path}, {} by {} pixels", picture.cols(), picture.rows());
+    Ok(())
+}
+
+fn inventory(args: &[String]) -> Result<(), String> {
+    let (mut input, mut format, mut bins) = (None, Format::Text, 10);
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => input = Some(it.next().ok_or("--input needs a value")?.as_str()),
+            "--format" => format = it.next().ok_or("--format needs a value")?.parse()?,
+            "--bins" => bins = number(arg, it.next())?,
+            _ => return Err(String::from("Usage: aoc inventory [--input <path|->] [--format <text|json|csv>] [--bins <n>]")),
+        }
+    }
+    let indata = input::read_input(1, input)?;
+    let report = inventory::from_input(&indata, bins).map_err(|e| e.to_string())?.ok_or("No elves in the inventory")?;
+    for line in inventory::format_report(format, &report) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn teams(args: &[String]) -> Result<(), String> {
+    let (mut input, mut format, mut method, mut k) = (None, Format::Text, Method::Auto, None);
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => input = Some(it.next().ok_or("--input needs a value")?.as_str()),
+            "--format" => format = it.next().ok_or("--format needs a value")?.parse()?,
+            "--method" => method = it.next().ok_or("--method needs a value")?.parse()?,
+            value if k.is_none() && !value.starts_with("--") => k = Some(value.parse().map_err(|_| format!("Invalid number of teams: {value}"))?),
+            _ => return Err(String::from("Usage: aoc teams <k> [--method <auto|exact|greedy|kk>] [--input <path|->] [--format <text|json|csv>]")),
+        }
+    }
+    let k = k.ok_or("No number of teams given")?;
+    let indata = input::read_input(1, input)?;
+    let elves = inventory::elves(&indata).map_err(|e| e.to_string())?;
+    for line in teams::format_teams(format, &teams::split(&elves, k, method)?) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn interpret(args: &[String]) -> Result<(), String> {
+    let (mut input, mut format, mut game, mut top) = (None, Format::Text, day2::Game::default(), usize::MAX);
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => input = Some(it.next().ok_or("--input needs a value")?.as_str()),
+            "--format" => format = it.next().ok_or("--format needs a value")?.parse()?,
+            "--game" => game = day2::Game::named(it.next().ok_or("--game needs a value")?)?,
+            "--top" => top = number(arg, it.next())?,
+            _ => return Err(String::from("Usage: aoc interpret [--game <name|path>] [--top <n>] [--input <path|->] [--format <text|json|csv>]")),
+        }
+    }
+    let indata = input::read_input(2, input)?;
+    let strategy = day2::parse_indata(&game, &indata).map_err(|e| e.to_string())?;
+    for line in interpret::format_readings(format, &interpret::readings(&game, &strategy)?, top) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn frames(args: &[String]) -> Result<(), String> {
+    let (mut format, mut steps, mut size, mut scale, mut input) = (image::Format::Png, None, (160, 90), 4, None);
+    let mut positional = vec![];
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--format" => format = it.next().ok_or("--format needs a value")?.parse()?,
+            "--steps" => steps = Some(number(arg, it.next())?),
+            "--scale" => scale = number(arg, it.next())?,
+            "--size" => {
+                let value = it.next().ok_or("--size needs a value")?;
+                let parsed = value.split_once('x').and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)));
+                size = parsed.filter(|(w, h)| *w > 0 && *h > 0).ok_or(format!("Invalid size: {value}, expected <width>x<height>"))?;
+            }
+            "--input" => input = Some(it.next().ok_or("--input needs a value")?.as_str()),
+            _ => positional.push(arg),
+        }
+    }
+    let [day, dir] = positional[..] else {
+        return Err(String::from("Usage: aoc frames <day> <dir> [--format <png|ppm|pgm>] [--steps <n>] [--size <w>x<h>] [--scale <n>] [--input <path>]"));
+    };
+    let day: u32 = day.parse().map_err(|_| format!("Invalid day: {day}"))?;
+    let indata = input::read_input(day, input)?;
+    let mut frames = Frames::new(Path::new(dir), format)?;
+    let mut record = |scene: &mut dyn Scene| {
+        let steps = steps.unwrap_or_else(|| scene.steps_per_frame());
+        image::record(scene, &mut frames, steps, size, scale)
+    };
+    match day {
+        9 => record(&mut day9::RopeScene::new(&day9::parse_indata(&indata).map_err(|e| e.to_string())?, 10))?,
+        10 => record(&mut day10::CrtScene::new(day10::play_commands(&day10::parse_indata(&indata).map_err(|e| e.to_string())?)))?,
+        12 => {
+            let (grid, start, end) = day12::parse_maze(&indata).map_err(|e| e.to_string())?;
+            record(&mut day12::ClimbScene::new(&grid, vec![start], end))?
+        }
+        14 => record(&mut day14::SandScene::new(day14::Cave::from(&day14::parse_indata(&indata).map_err(|e| e.to_string())?)))?,
+        _ => return Err(format!("There is no animation of day {day}, only of days 9, 10, 12 and 14")),
+    }
+    println!("Wrote {} frames to {dir}", frames.count);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    input::or_exit(match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("generate") => generate_input(&args[1..]),
+        Some("difftest") => difftest(&args[1..]),
+        Some("extract") => extract(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("new") => new_day(&args[1..]),
+        Some("watch") => watch_day(&args[1..]),
+        Some("image") => image(&args[1..]),
+        Some("frames") => frames(&args[1..]),
+        Some("inventory") => inventory(&args[1..]),
+        Some("teams") => teams(&args[1..]),
+        Some("interpret") => interpret(&args[1..]),
+        _ => Err(String::from(USAGE)),
+    });
+}
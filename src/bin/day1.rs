@@ -1,58 +1,15 @@
-use std::fs;
-
-fn parse_indata(indata: &str) -> Vec<Vec<i32>> {
-    indata
-        .split("\n\n")
-        .map(|b| {
-            b.split('\n')
-                .filter(|v| !v.is_empty())
-                .map(|v| v.parse::<i32>().unwrap())
-                .collect()
-        })
-        .collect()
-}
-
-fn process(indata: &str) -> (i32, i32) {
-    let mut sums: Vec<i32> = parse_indata(indata)
-        .iter()
-        .map(|v| v.iter().sum::<i32>())
-        .collect();
-    sums.sort_by(|a, b| b.cmp(a));
-    (sums[0], sums[0..3].iter().sum::<i32>())
-}
+use advent_of_code_2022::{day1::*, input, solution::Answer};
+use std::env;
 
+// Reads the input a line at a time instead of all at once, so it can be of any size.
 fn main() {
-    let indata = fs::read_to_string("data/day1.txt").expect("No indata");
-    let (p1, p2) = process(&indata);
-    println!("Part1: {}", p1);
-    println!("Part2: {}", p2);
-}
-
-#[cfg(test)]
-mod tests {
-    use indoc::indoc;
-
-    #[test]
-    fn test_example() {
-        let test_data = indoc! {r#"
-        1000
-        2000
-        3000
-        
-        4000
-        
-        5000
-        6000
-        
-        7000
-        8000
-        9000
-        
-        10000
-        "#
-        };
-        let (p1, p2) = super::process(test_data);
-        assert_eq!(24000, p1);
-        assert_eq!(45000, p2);
-    }
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut day1 = Day1::default();
+    let (path, options) = input::or_exit(input::parse_args(&args));
+    input::or_exit(input::set_options(&mut day1, &options));
+    let top = input::or_exit(input::open_input(1, path).and_then(|r| top_elves(r, day1.top).map_err(|e| e.to_string())));
+    println!("Part1: {}", largest(&top));
+    println!("Part2: {}", top_total(&top).map_or(Answer::Unsolved, Answer::from));
+    let elves: Vec<String> = top.iter().map(|e| format!("elf {} with {}", e.number, e.total)).collect();
+    println!("Top {}: {}", day1.top, elves.join(", "));
 }
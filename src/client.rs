@@ -0,0 +1,454 @@
+use regex::Regex;
+use std::{
+    env, fmt, fs,
+    io::{Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const YEAR: u32 = 2022;
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+pub const URL_VAR: &str = "AOC_URL";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+// The least time between two requests, also between runs of the program.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), " aoc client");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+pub trait Http {
+    fn send(&self, method: &str, url: &str, headers: &[(&str, String)], body: Option<&str>) -> Result<Response, String>;
+}
+
+// HTTP/1.1 over a plain TCP connection, closed after every request.
+pub struct PlainHttp;
+
+impl Http for PlainHttp {
+    fn send(&self, method: &str, url: &str, headers: &[(&str, String)], body: Option<&str>) -> Result<Response, String> {
+        let rest = url.strip_prefix("http://").ok_or(format!("{url}: only http URLs are supported"))?;
+        let (host, path) = match rest.find('/') {
+            Some(ix) => (&rest[..ix], &rest[ix..]),
+            None => (rest, "/"),
+        };
+        let addr = if host.contains(':') { String::from(host) } else { format!("{host}:80") };
+        let mut stream = TcpStream::connect(&addr).map_err(|e| format!("{addr}: {e}"))?;
+        stream.set_read_timeout(Some(Duration::from_secs(30))).map_err(|e| e.to_string())?;
+
+        let mut request = format!("{method} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n");
+        for (name, value) in headers {
+            request += &format!("{name}: {value}\r\n");
+        }
+        if let Some(body) = body {
+            request += &format!("Content-Length: {}\r\n", body.len());
+        }
+        request += "\r\n";
+        request += body.unwrap_or("");
+        stream.write_all(request.as_bytes()).map_err(|e| format!("{addr}: {e}"))?;
+
+        let mut raw = vec![];
+        stream.read_to_end(&mut raw).map_err(|e| format!("{addr}: {e}"))?;
+        parse_response(&String::from_utf8_lossy(&raw))
+    }
+}
+
+fn decode_chunked(mut body: &str) -> Result<String, String> {
+    let mut res = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n").ok_or("Truncated chunk")?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap().trim(), 16)
+            .map_err(|_| format!("Invalid chunk size '{size}'"))?;
+        if size == 0 {
+            return Ok(res);
+        }
+        res += rest.get(..size).ok_or("Truncated chunk")?;
+        body = rest[size..].strip_prefix("\r\n").ok_or("Missing line break after chunk")?;
+    }
+}
+
+pub fn parse_response(raw: &str) -> Result<Response, String> {
+    let (head, body) = raw.split_once("\r\n\r\n").ok_or("Incomplete HTTP response")?;
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse().ok())
+        .ok_or("Invalid HTTP status line")?;
+    let chunked = lines.any(|l| {
+        let l = l.to_ascii_lowercase();
+        l.starts_with("transfer-encoding:") && l.contains("chunked")
+    });
+    let body = if chunked { decode_chunked(body)? } else { String::from(body) };
+    Ok(Response { status, body })
+}
+
+// Anything curl supports, the headers and the body go through stdin so that the
+// session token does not show up in the process list.
+pub struct Curl;
+
+fn curl_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Http for Curl {
+    fn send(&self, method: &str, url: &str, headers: &[(&str, String)], body: Option<&str>) -> Result<Response, String> {
+        let mut config = String::new();
+        for (name, value) in headers {
+            config += &format!("header = {}\n", curl_quote(&format!("{name}: {value}")));
+        }
+        if let Some(body) = body {
+            config += &format!("data-binary = {}\n", curl_quote(body));
+        }
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--config", "-", "--request", method, "--write-out", "\n%{http_code}", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("curl: {e}"))?;
+        child.stdin.take().unwrap().write_all(config.as_bytes()).map_err(|e| format!("curl: {e}"))?;
+        let output = child.wait_with_output().map_err(|e| format!("curl: {e}"))?;
+        if !output.status.success() {
+            return Err(format!("curl: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout.rsplit_once('\n').ok_or("curl: no status code")?;
+        let status = status.trim().parse().map_err(|_| format!("curl: invalid status code '{status}'"))?;
+        Ok(Response { status, body: String::from(body) })
+    }
+}
+
+pub fn http_for(url: &str) -> Box<dyn Http> {
+    match url.starts_with("http://") {
+        true => Box::new(PlainHttp),
+        false => Box::new(Curl),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Feedback {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // Answered too soon after the last one, with the time left to wait.
+    TooSoon(String),
+    // The part is already solved or not unlocked yet.
+    WrongLevel,
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Feedback::Correct => write!(f, "That's the right answer"),
+            Feedback::Wrong => write!(f, "That's not the right answer"),
+            Feedback::TooHigh => write!(f, "That's not the right answer, it is too high"),
+            Feedback::TooLow => write!(f, "That's not the right answer, it is too low"),
+            Feedback::TooSoon(wait) => write!(f, "Answered too recently, {wait} left to wait"),
+            Feedback::WrongLevel => write!(f, "That part is already solved or not unlocked yet"),
+        }
+    }
+}
+
+impl Feedback {
+    fn from_page(html: &str) -> Result<Self, String> {
+        if html.contains("That's the right answer") {
+            Ok(Feedback::Correct)
+        } else if html.contains("You gave an answer too recently") {
+            let wait = Regex::new(r"You have (.*?) left to wait").unwrap();
+            Ok(Feedback::TooSoon(wait.captures(html).map_or(String::from("some time"), |c| String::from(&c[1]))))
+        } else if html.contains("That's not the right answer") {
+            Ok(match html {
+                h if h.contains("answer is too high") => Feedback::TooHigh,
+                h if h.contains("answer is too low") => Feedback::TooLow,
+                _ => Feedback::Wrong,
+            })
+        } else if html.contains("You don't seem to be solving the right level") {
+            Ok(Feedback::WrongLevel)
+        } else {
+            Err(String::from("Unexpected reply to the answer"))
+        }
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        match self {
+            Feedback::Correct => Some("correct"),
+            Feedback::Wrong => Some("wrong"),
+            Feedback::TooHigh => Some("too-high"),
+            Feedback::TooLow => Some("too-low"),
+            Feedback::TooSoon(_) | Feedback::WrongLevel => None,
+        }
+    }
+}
+
+// The answers submitted so far, one "<day> <part> <feedback> <answer>" per line.
+#[derive(Debug, Default, PartialEq)]
+pub struct Submissions(Vec<(u32, u32, Feedback, String)>);
+
+impl Submissions {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut res = vec![];
+        for (ix, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            let feedback = match fields.get(2) {
+                Some(&"correct") => Feedback::Correct,
+                Some(&"wrong") => Feedback::Wrong,
+                Some(&"too-high") => Feedback::TooHigh,
+                Some(&"too-low") => Feedback::TooLow,
+                _ => return Err(format!("line {}: expected correct, wrong, too-high or too-low", ix + 1)),
+            };
+            match (fields[0].parse(), fields[1].parse(), fields.get(3)) {
+                (Ok(day), Ok(part), Some(answer)) => res.push((day, part, feedback, String::from(*answer))),
+                _ => return Err(format!("line {}: expected '<day> <part> <feedback> <answer>'", ix + 1)),
+            }
+        }
+        Ok(Self(res))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    fn line(day: u32, part: u32, feedback: &str, answer: &str) -> String {
+        format!("{day} {part} {feedback} {answer}\n")
+    }
+
+    // Why `answer` must not be submitted, if the recorded feedback already tells.
+    pub fn refusal(&self, day: u32, part: u32, answer: &str) -> Option<String> {
+        let number = answer.parse::<i64>().ok();
+        for (_, _, feedback, previous) in self.0.iter().filter(|(d, p, _, _)| (*d, *p) == (day, part)) {
+            let bound = previous.parse::<i64>().ok().zip(number);
+            match feedback {
+                Feedback::Correct => return Some(format!("Day {day} part {part} is already solved, the answer was {previous}")),
+                _ if previous == answer => return Some(format!("{answer} was already submitted, {}", feedback.to_string().to_lowercase())),
+                Feedback::TooHigh if bound.is_some_and(|(p, n)| n >= p) => return Some(format!("{answer} is too high, {previous} already was")),
+                Feedback::TooLow if bound.is_some_and(|(p, n)| n <= p) => return Some(format!("{answer} is too low, {previous} already was")),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+// How long to wait before the next request when the last one was at `last`.
+pub fn wait_time(last: Option<Duration>, now: Duration, interval: Duration) -> Duration {
+    last.map_or(Duration::ZERO, |last| (last + interval).saturating_sub(now))
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+pub struct Client {
+    http: Box<dyn Http>,
+    url: String,
+    session: String,
+    // Where the inputs are cached and the submissions and time of the last request kept.
+    dir: PathBuf,
+    pub min_interval: Duration,
+}
+
+impl Client {
+    pub fn new(http: Box<dyn Http>, url: &str, session: &str, dir: &Path) -> Self {
+        Self {
+            http,
+            url: String::from(url.trim_end_matches('/')),
+            session: String::from(session.trim()),
+            dir: dir.to_path_buf(),
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    // The session token is the value of the session cookie of a logged in browser.
+    pub fn from_env(dir: &Path) -> Result<Self, String> {
+        let session = env::var(SESSION_VAR).map_err(|_| format!("Set {SESSION_VAR} to the session cookie of adventofcode.com"))?;
+        let url = env::var(URL_VAR).unwrap_or_else(|_| String::from(DEFAULT_URL));
+        Ok(Self::new(http_for(&url), &url, &session, dir))
+    }
+
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day}.txt"))
+    }
+
+    pub fn submissions_path(&self) -> PathBuf {
+        self.dir.join("submissions.txt")
+    }
+
+    fn throttle(&self) -> Result<(), String> {
+        let path = self.dir.join(".last_request");
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let last = fs::read_to_string(&path).ok().and_then(|s| s.trim().parse().ok()).map(Duration::from_millis);
+        let wait = wait_time(last, now, self.min_interval);
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+        fs::create_dir_all(&self.dir).map_err(|e| format!("{}: {e}", self.dir.display()))?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        fs::write(&path, now.as_millis().to_string()).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    fn request(&self, method: &str, path: &str, body: Option<&str>) -> Result<Response, String> {
+        self.throttle()?;
+        let mut headers = vec![("Cookie", format!("session={}", self.session)), ("User-Agent", String::from(USER_AGENT))];
+        if body.is_some() {
+            headers.push(("Content-Type", String::from("application/x-www-form-urlencoded")));
+        }
+        self.http.send(method, &format!("{}{path}", self.url), &headers, body)
+    }
+
+    // The input of a day, downloaded only if it is not cached yet. Also tells whether
+    // it was downloaded.
+    pub fn fetch(&self, day: u32) -> Result<(String, bool), String> {
+        let path = self.input_path(day);
+        if let Ok(indata) = fs::read_to_string(&path) {
+            return Ok((indata, false));
+        }
+        let response = self.request("GET", &format!("/{YEAR}/day/{day}/input"), None)?;
+        match response.status {
+            200 => {}
+            400 | 401 | 403 => return Err(String::from("The session token was rejected")),
+            404 => return Err(format!("The input of day {day} is not available yet")),
+            status => return Err(format!("Download failed with HTTP status {status}")),
+        }
+        fs::write(&path, &response.body).map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok((response.body, true))
+    }
+
+    // Submits an answer unless the earlier submissions already tell how it would go,
+    // definite feedback is recorded.
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> Result<Feedback, String> {
+        let log = self.submissions_path();
+        if let Some(reason) = Submissions::load(&log)?.refusal(day, part, answer) {
+            return Err(reason);
+        }
+        let body = format!("level={part}&answer={}", url_encode(answer));
+        let response = self.request("POST", &format!("/{YEAR}/day/{day}/answer"), Some(&body))?;
+        if response.status != 200 {
+            return Err(format!("Submission failed with HTTP status {}", response.status));
+        }
+        let feedback = Feedback::from_page(&response.body)?;
+        if let Some(name) = feedback.name() {
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&log)
+                .map_err(|e| format!("{}: {e}", log.display()))?;
+            file.write_all(Submissions::line(day, part, name, answer).as_bytes())
+                .map_err(|e| format!("{}: {e}", log.display()))?;
+        }
+        Ok(feedback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::TcpListener, sync::mpsc};
+
+    // Reads the head of a request and as much of the body as its Content-Length says.
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut request = String::new();
+        let mut buf = [0; 4096];
+        loop {
+            if let Some(end) = request.find("\r\n\r\n") {
+                let length = request[..end].lines().find_map(|l| l.strip_prefix("Content-Length: "));
+                if request.len() >= end + 4 + length.map_or(0, |n| n.parse().unwrap()) {
+                    return request;
+                }
+            }
+            let n = stream.read(&mut buf).unwrap();
+            request += &String::from_utf8_lossy(&buf[..n]);
+        }
+    }
+
+    // Answers requests with the canned replies in turn and passes the requests on.
+    fn stub_server(replies: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in replies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = read_request(&mut stream);
+                tx.send(request).unwrap();
+                let reply = format!("HTTP/1.1 {status} X\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+                stream.write_all(reply.as_bytes()).unwrap();
+            }
+        });
+        (url, rx)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-client-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_fetch() {
+        let dir = temp_dir("fetch");
+        let (url, requests) = stub_server(vec![(200, "1\n2\n"), (404, "Not found")]);
+        let mut client = Client::new(Box::new(PlainHttp), &url, "abc\n", &dir);
+        client.min_interval = Duration::ZERO;
+
+        assert_eq!(Ok((String::from("1\n2\n"), true)), client.fetch(1));
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert_eq!(Ok((String::from("1\n2\n"), false)), client.fetch(1));
+        assert_eq!(Err(String::from("The input of day 2 is not available yet")), client.fetch(2));
+        assert!(!client.input_path(2).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir = temp_dir("submit");
+        let (url, requests) = stub_server(vec![
+            (200, "<article><p>That's not the right answer; your answer is too high.</p></article>"),
+            (200, "<article><p>You gave an answer too recently. You have 42s left to wait.</p></article>"),
+            (200, "<article><p>That's the right answer!</p></article>"),
+        ]);
+        let mut client = Client::new(Box::new(PlainHttp), &url, "abc", &dir);
+        client.min_interval = Duration::ZERO;
+
+        assert_eq!(Ok(Feedback::TooHigh), client.submit(3, 1, "500"));
+        assert!(requests.recv().unwrap().ends_with("\r\n\r\nlevel=1&answer=500"));
+        assert!(client.submit(3, 1, "500").is_err());
+        assert!(client.submit(3, 1, "600").is_err());
+        assert_eq!(Ok(Feedback::TooSoon(String::from("42s"))), client.submit(3, 1, "400"));
+        assert_eq!(Ok(Feedback::Correct), client.submit(3, 1, "400"));
+        assert_eq!(Err(String::from("Day 3 part 1 is already solved, the answer was 400")), client.submit(3, 1, "300"));
+        assert_eq!("3 1 too-high 500\n3 1 correct 400\n", fs::read_to_string(client.submissions_path()).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_helpers() {
+        let raw = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n6;x=y\r\npedia!\r\n0\r\n\r\n";
+        assert_eq!(Ok(Response { status: 200, body: String::from("Wikipedia!") }), parse_response(raw));
+        assert!(parse_response("HTTP/1.1 200 OK\r\n").is_err());
+
+        let second = Duration::from_secs(1);
+        assert_eq!(Duration::ZERO, wait_time(None, second, 5 * second));
+        assert_eq!(2 * second, wait_time(Some(8 * second), 11 * second, 5 * second));
+        assert_eq!(Duration::ZERO, wait_time(Some(second), 11 * second, 5 * second));
+        assert_eq!("a%20b%2Bc", url_encode("a b+c"));
+    }
+}
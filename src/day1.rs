@@ -0,0 +1,199 @@
+use std::{cmp::{Ordering, Reverse}, collections::BinaryHeap, io::BufRead};
+
+use crate::{
+    error::{ParseError, Source},
+    solution::{option_value, unknown_option, Answer, Solution},
+};
+
+pub fn parse_indata(indata: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    let src = Source::new(Day1::DAY, indata);
+    indata
+        .split("\n\n")
+        .map(|b| {
+            b.split('\n')
+                .filter(|v| !v.is_empty())
+                .map(|v| src.number(v))
+                .collect()
+        })
+        .collect()
+}
+
+// An elf and the calories it carries, elves are numbered from 1 in input order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    pub number: usize,
+    pub total: i64,
+}
+
+// By total, and the elf that comes first wins a tie.
+impl Ord for Elf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total.cmp(&other.total).then(other.number.cmp(&self.number))
+    }
+}
+
+impl PartialOrd for Elf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The `n` best elves seen so far, in a heap with the least of them on top.
+pub struct TopN {
+    n: usize,
+    heap: BinaryHeap<Reverse<Elf>>,
+}
+
+impl TopN {
+    pub fn new(n: usize) -> Self {
+        Self { n, heap: BinaryHeap::with_capacity(n + 1) }
+    }
+
+    pub fn push(&mut self, elf: Elf) {
+        self.heap.push(Reverse(elf));
+        if self.heap.len() > self.n {
+            self.heap.pop();
+        }
+    }
+
+    // Best first.
+    pub fn into_sorted(self) -> Vec<Elf> {
+        self.heap.into_sorted_vec().into_iter().map(|Reverse(elf)| elf).collect()
+    }
+}
+
+// Reads the inventory a line at a time and keeps only the `n` largest totals, so the
+// input can be of any size. Blank lines separate the elves, several in a row count as one.
+pub fn top_elves(reader: impl BufRead, n: usize) -> Result<Vec<Elf>, ParseError> {
+    let mut top = TopN::new(n);
+    let (mut count, mut current) = (0, None);
+    for (ix, line) in reader.lines().enumerate() {
+        let on_line = |e: ParseError| ParseError { line: ix + 1, ..e };
+        let line = line.map_err(|e| ParseError { day: Day1::DAY, line: ix + 1, column: 1, text: String::new(), message: e.to_string() })?;
+        let line = line.trim_end();
+        let src = Source::new(Day1::DAY, line);
+        if line.is_empty() {
+            if let Some(total) = current.take() {
+                count += 1;
+                top.push(Elf { number: count, total });
+            }
+            continue;
+        }
+        let calories: i64 = src.number(line).map_err(on_line)?;
+        let total = current.unwrap_or(0i64).checked_add(calories);
+        current = Some(total.ok_or_else(|| on_line(src.error(line, "Total calories too large")))?);
+    }
+    if let Some(total) = current {
+        top.push(Elf { number: count + 1, total });
+    }
+    Ok(top.into_sorted())
+}
+
+pub fn process(indata: &str) -> Result<(i64, Option<i64>), ParseError> {
+    let top = top_elves(indata.as_bytes(), 3)?;
+    Ok((largest(&top), top_total(&top)))
+}
+
+// What the best elf carries, or nothing without elves.
+pub fn largest(top: &[Elf]) -> i64 {
+    top.first().map_or(0, |e| e.total)
+}
+
+// What the top elves carry together, None when it does not fit in an i64.
+pub fn top_total(top: &[Elf]) -> Option<i64> {
+    top.iter().try_fold(0i64, |sum, e| sum.checked_add(e.total))
+}
+
+pub struct Day1 {
+    pub top: usize,
+}
+
+impl Default for Day1 {
+    fn default() -> Self {
+        Self { top: 3 }
+    }
+}
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Input = Vec<Elf>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        top_elves(indata.as_bytes(), self.top)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        largest(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        top_total(input).map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn options(&self) -> Vec<(&'static str, String)> {
+        vec![("top", self.top.to_string())]
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "top" => match option_value(name, value)? {
+                0 => return Err(String::from("--top must be at least 1")),
+                top => self.top = top,
+            },
+            _ => return Err(unknown_option(name, &self.options())),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_example() {
+        let test_data = indoc! {r#"
+        1000
+        2000
+        3000
+        
+        4000
+        
+        5000
+        6000
+        
+        7000
+        8000
+        9000
+        
+        10000
+        "#
+        };
+        let (p1, p2) = super::process(test_data).unwrap();
+        assert_eq!(24000, p1);
+        assert_eq!(Some(45000), p2);
+
+        let top = top_elves(test_data.as_bytes(), 2).unwrap();
+        assert_eq!(vec![Elf { number: 4, total: 24000 }, Elf { number: 3, total: 11000 }], top);
+    }
+
+    #[test]
+    fn test_top_elves() {
+        assert_eq!((5, Some(5)), process("5\n").unwrap());
+        assert_eq!((0, Some(0)), process("").unwrap());
+        let top = top_elves("1\r\n\r\n\r\n2\n\n1\n".as_bytes(), 5).unwrap();
+        assert_eq!(vec![Elf { number: 2, total: 2 }, Elf { number: 1, total: 1 }, Elf { number: 3, total: 1 }], top);
+
+        let big = "4000000000\n4000000000\n\n1\n";
+        assert_eq!((8000000000, Some(8000000001)), process(big).unwrap());
+        let large = "9223372036854775807\n\n9223372036854775806\n";
+        assert_eq!((i64::MAX, None), process(large).unwrap());
+        let day = Day1::default();
+        assert_eq!(Answer::Unsolved, day.part2(&day.parse(large).unwrap()));
+        let err = process("9223372036854775807\n1\n").unwrap_err();
+        assert_eq!((2, "Total calories too large"), (err.line, err.message.as_str()));
+        let err = process("1\n\n2x\n").unwrap_err();
+        assert_eq!((3, 1, "2x"), (err.line, err.column, err.text.as_str()));
+    }
+}
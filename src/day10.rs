@@ -0,0 +1,342 @@
+use regex::Regex;
+
+use crate::{
+    error::{ParseError, Source},
+    grid::Grid,
+    solution::{Answer, Solution},
+    visualize::{Canvas, Pos, Scene},
+};
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Noop,
+    AddX(i32),
+}
+
+pub fn parse_indata(indata: &str) -> Result<Vec<Command>, ParseError> {
+    let src = Source::new(Day10::DAY, indata);
+    let re = Regex::new(r"^(noop|addx ([-]?\d+))$").unwrap();
+    let mut commands = Vec::new();
+    for l in src.lines() {
+        let cap = src.captures(&re, l, "noop' or 'addx <value>")?;
+        match cap.get(2) {
+            None => commands.push(Command::Noop),
+            Some(value) => {
+                commands.push(Command::Noop);
+                commands.push(Command::AddX(src.number(value.as_str())?));
+            }
+        }
+    }
+    Ok(commands)
+}
+
+
+pub fn play_commands(commands: &Vec<Command>) -> Vec<i32> {
+    let mut x = 1;
+    let mut rep = Vec::new();
+    for cmd in commands {
+        match cmd {
+            Command::Noop => {
+            },
+            Command::AddX(nr) => {
+                x += nr;
+            },
+        };
+        rep.push(x);
+    }
+    rep
+}
+
+// Cycles after the end of the program are left out.
+pub fn signal_strength(xs: &[i32]) -> i32 {
+    let mut res = 0;
+    for i in 0..=5 {
+        let index = 40*i+20;
+        if index > xs.len() {
+            break;
+        }
+        let value = xs[index-2];
+        res += value * (index as i32);
+    }
+    res
+}
+
+// The middle of the sprite while the pixel of `cycle` is drawn, counting from 0. It
+// moves at the end of a cycle.
+pub fn sprite(xs: &[i32], cycle: usize) -> i32 {
+    cycle.checked_sub(1).map_or(1, |c| xs[c])
+}
+
+pub fn is_lit(xs: &[i32], cycle: usize) -> bool {
+    let sprite = sprite(xs, cycle);
+    (sprite - 1..=sprite + 1).contains(&((cycle % 40) as i32))
+}
+
+pub fn render_screen(xs: &[i32]) -> Vec<String> {
+    let rows = xs.len() / 40;
+    let mut screen = Grid::new(rows, 40, false);
+    for cycle in 0..rows * 40 {
+        screen[(cycle / 40, cycle % 40)] = is_lit(xs, cycle);
+    }
+    screen.render(|lit| if *lit { '#' } else { '.' })
+}
+
+// The CRT drawing one pixel per cycle, with the sprite on the line below the screen.
+pub struct CrtScene {
+    xs: Vec<i32>,
+    cycle: usize,
+}
+
+impl CrtScene {
+    pub fn new(xs: Vec<i32>) -> Self {
+        Self { xs, cycle: 0 }
+    }
+}
+
+impl Scene for CrtScene {
+    fn step(&mut self) -> bool {
+        self.cycle += 1;
+        self.cycle < (self.xs.len() / 40) * 40
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        let rows = self.xs.len() / 40;
+        for cycle in 0..rows * 40 {
+            let ch = match cycle {
+                c if c < self.cycle => if is_lit(&self.xs, c) { '#' } else { '.' },
+                c if c == self.cycle => '@',
+                _ => ' ',
+            };
+            canvas.set(Pos::new((cycle % 40) as i32, (cycle / 40) as i32), ch);
+        }
+        if self.cycle < rows * 40 {
+            let sprite = sprite(&self.xs, self.cycle);
+            for x in (sprite - 1..=sprite + 1).filter(|x| (0..40).contains(x)) {
+                canvas.set(Pos::new(x, rows as i32 + 1), '=');
+            }
+        }
+    }
+
+    fn status(&self) -> String {
+        format!("Cycle {}, X = {}", self.cycle + 1, sprite(&self.xs, self.cycle))
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Vec<Command>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        signal_strength(&play_commands(input)).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        render_screen(&play_commands(input)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+
+    const TEST_DATA: &str = indoc! {r#"
+        addx 15
+        addx -11
+        addx 6
+        addx -3
+        addx 5
+        addx -1
+        addx -8
+        addx 13
+        addx 4
+        noop
+        addx -1
+        addx 5
+        addx -1
+        addx 5
+        addx -1
+        addx 5
+        addx -1
+        addx 5
+        addx -1
+        addx -35
+        addx 1
+        addx 24
+        addx -19
+        addx 1
+        addx 16
+        addx -11
+        noop
+        noop
+        addx 21
+        addx -15
+        noop
+        noop
+        addx -3
+        addx 9
+        addx 1
+        addx -3
+        addx 8
+        addx 1
+        addx 5
+        noop
+        noop
+        noop
+        noop
+        noop
+        addx -36
+        noop
+        addx 1
+        addx 7
+        noop
+        noop
+        noop
+        addx 2
+        addx 6
+        noop
+        noop
+        noop
+        noop
+        noop
+        addx 1
+        noop
+        noop
+        addx 7
+        addx 1
+        noop
+        addx -13
+        addx 13
+        addx 7
+        noop
+        addx 1
+        addx -33
+        noop
+        noop
+        noop
+        addx 2
+        noop
+        noop
+        noop
+        addx 8
+        noop
+        addx -1
+        addx 2
+        addx 1
+        noop
+        addx 17
+        addx -9
+        addx 1
+        addx 1
+        addx -3
+        addx 11
+        noop
+        noop
+        addx 1
+        noop
+        addx 1
+        noop
+        noop
+        addx -13
+        addx -19
+        addx 1
+        addx 3
+        addx 26
+        addx -30
+        addx 12
+        addx -1
+        addx 3
+        addx 1
+        noop
+        noop
+        noop
+        addx -9
+        addx 18
+        addx 1
+        addx 2
+        noop
+        noop
+        addx 9
+        noop
+        noop
+        noop
+        addx -1
+        addx 2
+        addx -37
+        addx 1
+        addx 3
+        noop
+        addx 15
+        addx -21
+        addx 22
+        addx -6
+        addx 1
+        noop
+        addx 2
+        addx 1
+        noop
+        addx -10
+        noop
+        noop
+        addx 20
+        addx 1
+        addx 2
+        addx 2
+        addx -6
+        addx -11
+        noop
+        noop
+        noop
+        "#
+    };
+
+    #[test]
+    fn test_part1() {
+        let commands = parse_indata(TEST_DATA).unwrap();
+        let xs = play_commands(&commands);
+        let res = signal_strength(&xs);
+        assert_eq!(13140, res);
+    }
+
+    #[test]
+    fn test_part2() {
+        let commands = parse_indata(TEST_DATA).unwrap();
+        let xs = play_commands(&commands);
+        let screen = render_screen(&xs);
+
+        let test_data: &'static str = indoc! {r#"
+            ##..##..##..##..##..##..##..##..##..##..
+            ###...###...###...###...###...###...###.
+            ####....####....####....####....####....
+            #####.....#####.....#####.....#####.....
+            ######......######......######......####
+            #######.......#######.......#######....."#
+        };
+
+        assert_eq!(test_data, screen.join("\n"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("noop\naddx 3\naddx x\n").unwrap_err();
+        assert_eq!((3, 1), (err.line, err.column));
+        assert_eq!("Expected 'noop' or 'addx <value>'", err.message);
+    }
+
+    #[test]
+    fn test_short_program() {
+        let commands = parse_indata("noop\n").unwrap();
+        assert_eq!(Answer::Int(0), Day10.part1(&commands));
+        let commands = parse_indata(&"noop\n".repeat(25)).unwrap();
+        assert_eq!(Answer::Int(20), Day10.part1(&commands));
+        // Less than a row of pixels.
+        assert_eq!(Answer::Bitmap(vec![]), Day10.part2(&commands));
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{HashMap, hash_map::Entry};
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::{
+    error::{ParseError, Source},
+    solution::{Answer, Solution},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Op {
+    Add(i64),
+    Mul(i64),
+    Sq,
+    X2,
+}
+#[derive(Debug, PartialEq, Clone)]
+pub struct Monkey {
+    pub id: i64,
+    pub op: Op,
+    pub div_by: i64,
+    pub monkey_iftrue: i64,
+    pub monkey_iffalse: i64,
+}
+
+const MONKEY_LINES: [(&str, &str); 6] = [
+    (r"^Monkey (\d+):$", "Monkey <id>:"),
+    (r"^[ ]*Starting items: (.*)$", "Starting items: <items>"),
+    (r"^[ ]*Operation: new = old (\*|\+) (\d+|old)$", "Operation: new = old <*|+> <value|old>"),
+    (r"^[ ]*Test: divisible by (\d+)$", "Test: divisible by <value>"),
+    (r"^[ ]*If true: throw to monkey (\d+)$", "If true: throw to monkey <id>"),
+    (r"^[ ]*If false: throw to monkey (\d+)$", "If false: throw to monkey <id>"),
+];
+
+pub type InData = (Vec<Monkey>, HashMap<i64, Vec<i64>>);
+
+pub fn parse_indata(indata: &str) -> Result<InData, ParseError> {
+    let src = Source::new(Day11::DAY, indata);
+    let regexes: Vec<Regex> = MONKEY_LINES.iter().map(|(re, _)| Regex::new(re).unwrap()).collect();
+    let mut item_map: HashMap<i64, Vec<i64>> = HashMap::new();
+    let mut monkeys = Vec::new();
+    let mut targets = Vec::new();
+    let mut lines = src.lines();
+    while let Some(first) = lines.next() {
+        let mut cap = vec![src.captures(&regexes[0], first, MONKEY_LINES[0].1)?];
+        for (re, (_, expected)) in regexes.iter().zip(MONKEY_LINES).skip(1) {
+            let line = lines.next().unwrap_or(&indata[indata.len()..]);
+            cap.push(src.captures(re, line, expected)?);
+        }
+        let id: i64 = src.number(&cap[0][1])?;
+        let items: Vec<i64> = cap[1][1].split(',').map(|i| src.number(i.trim())).collect::<Result<_, _>>()?;
+        if item_map.insert(id, items).is_some() {
+            return Err(src.error(first, format!("Monkey {id} is defined twice")));
+        }
+        let op = match (&cap[2][1], &cap[2][2]) {
+            ("*", "old") => Op::Sq,
+            ("+", "old") => Op::X2,
+            ("*", n) => Op::Mul(src.number(n)?),
+            (_, n) => Op::Add(src.number(n)?),
+        };
+        let div_by = src.number(&cap[3][1])?;
+        if div_by == 0 {
+            return Err(src.error(&cap[3][1], "Can not test for divisibility by 0"));
+        }
+        let monkey_iftrue = src.number(&cap[4][1])?;
+        let monkey_iffalse = src.number(&cap[5][1])?;
+        targets.push((monkey_iftrue, cap[4].get(1).unwrap().as_str()));
+        targets.push((monkey_iffalse, cap[5].get(1).unwrap().as_str()));
+        monkeys.push(Monkey { id, op, div_by, monkey_iftrue, monkey_iffalse });
+    }
+    if let Some((id, at)) = targets.into_iter().find(|(id, _)| !item_map.contains_key(id)) {
+        return Err(src.error(at, format!("There is no monkey {id}")));
+    }
+    Ok((monkeys, item_map))
+}
+
+pub fn play_once(monkeys: &Vec<Monkey>, 
+    items: &HashMap<i64, Vec<i64>>, 
+    inspections: &HashMap<i64, i64>,
+    wf: &impl Fn (i64) -> i64) -> 
+    (HashMap<i64, Vec<i64>>, HashMap<i64, i64>) {
+    let mut next_items: HashMap<i64, Vec<i64>> = items.clone();
+    let mut next_inspections: HashMap<i64, i64> = inspections.clone();
+    for m in monkeys {
+        let changes: Vec<(i64, i64)> = if let Some(it) = next_items.get(&m.id) {
+            let mut res: Vec<(i64, i64)> = vec!(); 
+            for ii in  it {
+                let mut worry: i64 = *ii;
+                match m.op {
+                    Op::Add(n) => worry += n,
+                    Op::Mul(n) => worry *= n,
+                    Op::Sq => worry = worry * worry,
+                    Op::X2 => worry = worry + worry,
+                }
+                worry = wf(worry);
+                let monkey_id = if worry % m.div_by == 0 {
+                    m.monkey_iftrue
+                } else {
+                    m.monkey_iffalse
+                };
+                res.push((monkey_id, worry));
+                match next_inspections.get(&m.id) {
+                    Some(count) => { next_inspections.insert(m.id, count + 1); }
+                    None => { next_inspections.insert(m.id, 1); }
+                }
+            }
+            res
+        } else {
+            vec!()
+        };
+        for (monkey_id, worry) in changes {
+            next_items.remove(&m.id);
+            match next_items.entry(monkey_id) {
+                Entry::Vacant(e) => { e.insert(vec![worry]); },
+                Entry::Occupied(mut e) => { e.get_mut().push(worry); }
+            }
+        }
+    }
+    (next_items, next_inspections)
+}
+
+pub fn play(monkeys: &Vec<Monkey>, items: &HashMap<i64, Vec<i64>>, nr: usize, wf: &impl Fn (i64) -> i64) -> (HashMap<i64, Vec<i64>>, HashMap<i64,i64>) {
+    let mut mut_items = items.clone();
+    let mut mut_inspections: HashMap<i64, i64> = HashMap::new();
+    for _ in 0..nr {
+        (mut_items, mut_inspections) = play_once(monkeys, &mut_items, &mut_inspections, wf);
+    }
+    (mut_items, mut_inspections)
+}
+
+pub fn calc_monkey_business(inspections: &HashMap<i64, i64>) -> i64 {
+    inspections.values().copied().sorted().rev().take(2).product::<i64>()
+}
+
+pub fn worry_function_1(w: i64) -> i64 {  
+    w / 3
+}
+
+pub fn make_worry_function_2(monkeys: &[Monkey]) -> impl Fn (i64) -> i64 {
+    let modulo: i64 = monkeys.iter().map(|m| m.div_by).product();
+    move |w| w % modulo
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = InData;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let (monkeys, items) = input;
+        let (_, inspections) = play(monkeys, items, 20, &worry_function_1);
+        calc_monkey_business(&inspections).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let (monkeys, items) = input;
+        let worry_function = make_worry_function_2(monkeys);
+        let (_, inspections) = play(monkeys, items, 10_000, &worry_function);
+        calc_monkey_business(&inspections).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+        Monkey 0:
+        Starting items: 79, 98
+        Operation: new = old * 19
+        Test: divisible by 23
+            If true: throw to monkey 2
+            If false: throw to monkey 3
+
+        Monkey 1:
+        Starting items: 54, 65, 75, 74
+        Operation: new = old + 6
+        Test: divisible by 19
+            If true: throw to monkey 2
+            If false: throw to monkey 0
+
+        Monkey 2:
+        Starting items: 79, 60, 97
+        Operation: new = old * old
+        Test: divisible by 13
+            If true: throw to monkey 1
+            If false: throw to monkey 3
+
+        Monkey 3:
+        Starting items: 74
+        Operation: new = old + 3
+        Test: divisible by 17
+            If true: throw to monkey 0
+            If false: throw to monkey 1
+    "#
+    };
+
+    #[test]
+    fn test_part1() {
+        let (monkeys, items) = parse_indata(TEST_DATA).unwrap();
+        let (items, inspections) = play(&monkeys, &items, 20, &worry_function_1);
+        assert_eq!(2, items.len());
+        assert_eq!(vec!(10, 12, 14, 26, 34), items[&0]);
+        assert_eq!(vec!(245, 93, 53, 199, 115), items[&1]);
+        assert_eq!(10605, calc_monkey_business(&inspections));
+    }
+
+    #[test]
+    fn test_part2() {
+        let (monkeys, items) = parse_indata(TEST_DATA).unwrap();
+        {
+            let (_items, inspections) = play(&monkeys, &items, 20, &make_worry_function_2(&monkeys));
+            assert_eq!(103*99, calc_monkey_business(&inspections));
+        }
+        {
+            let (_items, inspections) = play(&monkeys, &items, 1_000, &make_worry_function_2(&monkeys));
+            assert_eq!(5192*5204, calc_monkey_business(&inspections));
+        }
+        {
+            let (_items, inspections) = play(&monkeys, &items, 10_000, &make_worry_function_2(&monkeys));
+            assert_eq!(52013*52166, calc_monkey_business(&inspections));
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata(&TEST_DATA.replace("divisible by 23", "divisible by 0")).unwrap_err();
+        assert_eq!((4, 20), (err.line, err.column));
+        assert_eq!("Can not test for divisibility by 0", err.message);
+
+        let err = parse_indata(&TEST_DATA.replace("If true: throw to monkey 2", "If true: throw to monkey 9")).unwrap_err();
+        assert_eq!("There is no monkey 9", err.message);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    error::{ParseError, Source},
+    grid,
+    image::{Image, Palette},
+    pathfinding::{astar, bfs_multi, Graph, Path, Search},
+    point::Point2,
+    solution::{Answer, Solution},
+    visualize::{Canvas, Scene},
+};
+
+pub fn char_to_value(c: char) -> i32 {
+    let a = 'A' as i32;
+    (c as i32) - a
+}
+
+pub type Pos = Point2<i32>;
+
+pub type Grid = grid::Grid<i32>;
+
+pub fn parse_indata(indata: &str) -> Result<Grid, ParseError> {
+    let src = Source::new(Day12::DAY, indata);
+    let height = |c: char| (c.is_ascii_lowercase() || c == 'S' || c == 'E').then(|| char_to_value(c));
+    Grid::parse(&src, height, "a height a-z, 'S' or 'E'")
+}
+
+pub fn neighbours(grid: &Grid, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+    grid.neighbours4(p.to_cell()).map(Pos::from_cell)
+}
+
+pub fn find_char(grid: &Grid, ch: i32) -> Vec<Pos> {
+    grid.find(|v| *v == ch).map(Pos::from_cell).collect()
+}
+
+// The moves allowed in the heightmap, climbing at most one step up at a time.
+pub struct Climb<'a> {
+    pub grid: &'a Grid,
+}
+
+impl Graph for Climb<'_> {
+    type Node = Pos;
+
+    fn neighbours(&self, pos: &Pos) -> Vec<(Pos, usize)> {
+        let height = self.grid[pos.to_cell()];
+        neighbours(self.grid, *pos)
+            .filter(|p| self.grid[p.to_cell()] - height <= 1)
+            .map(|p| (p, 1))
+            .collect()
+    }
+
+    fn heuristic(&self, pos: &Pos, goal: &Pos) -> usize {
+        pos.manhattan(*goal) as usize
+    }
+}
+
+pub fn solve_maze(grid: &Grid, start: &Pos, end: &Pos) -> Option<Path<Pos>> {
+    astar(&Climb { grid }, *start, end)
+}
+
+pub fn parse_maze(indata: &str) -> Result<(Grid, Pos, Pos), ParseError> {
+    let mut grid = parse_indata(indata)?;
+    let find_one = |ch: char| match find_char(&grid, char_to_value(ch))[..] {
+        [pos] => Ok(pos),
+        _ => Err(Source::new(Day12::DAY, indata).error(indata, format!("Expected exactly one '{ch}'"))),
+    };
+    let start = find_one('S')?;
+    let end = find_one('E')?;
+
+    grid[start.to_cell()] = char_to_value('a');
+    grid[end.to_cell()] = char_to_value('z');
+    Ok((grid, start, end))
+}
+
+pub fn shortest_from_any(grid: &Grid, end: &Pos) -> Option<(Pos, usize)> {
+    let starts = find_char(grid, char_to_value('a'));
+    let path = bfs_multi(&Climb { grid }, starts).path(end)?;
+    Some((path.nodes[0], path.cost))
+}
+
+// The heightmap with `path` drawn on it in red.
+pub fn picture(grid: &Grid, path: &[Pos]) -> Image {
+    let mut image = grid.map(|h| Palette::TERRAIN.value(*h as i64, char_to_value('a') as i64, char_to_value('z') as i64));
+    for p in path {
+        image[p.to_cell()] = [220, 30, 30];
+    }
+    image
+}
+
+// The breadth first search spreading from the starts one step at a time, and the
+// shortest path to the end once it is reached.
+pub struct ClimbScene<'a> {
+    grid: &'a Grid,
+    end: Pos,
+    search: Search<Pos>,
+    steps: usize,
+    last: usize,
+}
+
+impl<'a> ClimbScene<'a> {
+    pub fn new(grid: &'a Grid, starts: Vec<Pos>, end: Pos) -> Self {
+        let search = bfs_multi(&Climb { grid }, starts);
+        let last = search.distance(&end).unwrap_or_else(|| search.dist.values().copied().max().unwrap_or(0));
+        Self { grid, end, search, steps: 0, last }
+    }
+}
+
+impl Scene for ClimbScene<'_> {
+    fn step(&mut self) -> bool {
+        self.steps += 1;
+        self.steps <= self.last
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        let done = self.steps > self.last;
+        for (row, col) in self.grid.positions() {
+            let p = Pos::from_cell((row, col));
+            let ch = match self.search.distance(&p) {
+                Some(0) => 'S',
+                Some(d) if d == self.steps && !done => '*',
+                Some(d) if d < self.steps && d <= self.last => '.',
+                _ => char::from_u32(('A' as i32 + self.grid[(row, col)]) as u32).unwrap_or('?'),
+            };
+            canvas.set(p, ch);
+        }
+        if done {
+            for p in self.search.path(&self.end).map(|path| path.nodes).unwrap_or_default().into_iter().skip(1) {
+                canvas.set(p, '#');
+            }
+        }
+        canvas.set(self.end, 'E');
+    }
+
+    fn status(&self) -> String {
+        match self.search.distance(&self.end) {
+            Some(d) if self.steps > self.last => format!("Reached E in {d} steps"),
+            None if self.steps > self.last => String::from("E can't be reached"),
+            _ => format!("Step {}", self.steps),
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input = (Grid, Pos, Pos);
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_maze(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let (grid, start, end) = input;
+        match solve_maze(grid, start, end) {
+            Some(path) => path.cost.into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let (grid, _, end) = input;
+        match shortest_from_any(grid, end) {
+            Some((_, steps)) => steps.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+    Sabqponm
+    abcryxxl
+    accszExk
+    acctuvwj
+    abdefghi
+    "#
+    };
+
+    #[test]
+    fn test_part1() {
+        let mut grid = parse_indata(TEST_DATA).unwrap();
+        let start = find_char(&grid, char_to_value('S'))[0];
+        let end = find_char(&grid, char_to_value('E'))[0];
+        assert_eq!(Pos::new(0, 0), start);
+        assert_eq!(Pos::new(5, 2), end);
+
+        grid[start.to_cell()] = char_to_value('a');
+        grid[end.to_cell()] = char_to_value('z');
+
+        let path = solve_maze(&grid, &start, &end).unwrap();
+        assert_eq!(31, path.cost);
+        assert_eq!(32, path.nodes.len());
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut grid = parse_indata(TEST_DATA).unwrap();
+        let start = find_char(&grid, char_to_value('S'))[0];
+        let end = find_char(&grid, char_to_value('E'))[0];
+        assert_eq!(Pos::new(0, 0), start);
+        assert_eq!(Pos::new(5, 2), end);
+
+        grid[start.to_cell()] = char_to_value('a');
+        grid[end.to_cell()] = char_to_value('z');
+
+        let mut res: Vec<(Pos, usize)> = find_char(&grid, char_to_value('a')).into_iter().filter_map(|sp| {
+            solve_maze(&grid, &sp, &end).map(|path| (sp, path.cost))
+        }).collect();
+        res.sort_by_key(|a| a.1);
+
+        assert_eq!(29, res[0].1);
+        assert_eq!(Some(res[0]), shortest_from_any(&grid, &end));
+    }
+
+    #[test]
+    fn test_scene() {
+        let (grid, start, end) = parse_maze(TEST_DATA).unwrap();
+        let mut scene = ClimbScene::new(&grid, vec![start], end);
+        let mut steps = 0;
+        while scene.step() {
+            steps += 1;
+        }
+        assert_eq!(31, steps);
+        let mut canvas = Canvas::default();
+        scene.draw(&mut canvas);
+        assert_eq!(vec!["S#######", "..######", "..###E##", "..######", "..######"], canvas.render(80, 10));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("Sab\naBE\n").unwrap_err();
+        assert_eq!((2, 2), (err.line, err.column));
+
+        let err = parse_maze("Sab\nabE\nabE\n").unwrap_err();
+        assert_eq!("Expected exactly one 'E'", err.message);
+    }
+}
@@ -0,0 +1,329 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    IResult, combinator::map_res, sequence::tuple, multi::{separated_list0, many0},
+};
+
+use crate::{
+    error::{ParseError, Source},
+    grid::Grid,
+    image::Image,
+    point::{Bounds2, Point2},
+    solution::{Answer, Solution},
+    visualize::{Canvas, Scene},
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Content {
+    Rock,
+    Sand,
+}
+
+pub type Pos = Point2<i32>;
+
+// The cave down to the floor, wide enough for the sand pile that ends up reaching START.
+pub struct Cave {
+    y_max: i32,
+    x_min: i32,
+    data: Grid<Option<Content>>,
+}
+
+impl Cave {
+    pub fn get(&self, p: &Pos) -> Option<&Content> {
+        if p.y < self.y_max + 2 {
+            self.data.get(p.y as isize, (p.x - self.x_min) as isize).and_then(|c| c.as_ref())
+        } else {
+            Some(&Content::Rock)
+        }
+    }
+
+    pub fn insert(&mut self, p: Pos, content: Content) {
+        self.data[(p.y as usize, (p.x - self.x_min) as usize)] = Some(content);
+    }
+
+    pub fn from(lst: &Vec<Vec<Pos>>) -> Self {
+        let mut rocks = Vec::new();
+        for l in lst {
+            for ss in l.windows(2) {
+                let mut p = ss[0];
+                let step = (ss[1] - p).signum();
+                assert!(step.x == 0 || step.y == 0);
+                loop {
+                    rocks.push(p);
+                    if p == ss[1] {
+                        break;
+                    }
+                    p += step;
+                }
+            }
+        }
+        // Sand piles up in a triangle below START, reaching the floor at y_max + 1.
+        let y_max = rocks.iter().map(|p| p.y).fold(START.y, i32::max);
+        let floor = Bounds2::new(START)
+            .extend(Pos::new(START.x - y_max - 2, y_max + 1))
+            .extend(Pos::new(START.x + y_max + 2, y_max + 1));
+        let bounds = rocks.iter().fold(floor, |b, p| b.extend(*p));
+        let x_min = bounds.min.x;
+
+        let data = Grid::new(bounds.height() as usize, bounds.width() as usize, None);
+        let mut cave = Self {y_max, x_min, data};
+        for p in rocks {
+            cave.insert(p, Content::Rock);
+        }
+        cave
+    }
+
+    pub fn occupied(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.data.find(|c| c.is_some()).map(|(y, x)| Pos::new(x as i32 + self.x_min, y as i32))
+    }
+}
+
+pub const START: Pos = Pos::new(500, 0);
+
+pub fn parse_integer(input: &str) -> IResult<&str, i32> {
+    map_res(digit1, |s: &str| s.parse())(input)
+}
+
+pub fn parse_tuple(input: &str) -> IResult<&str, Pos> {
+    let r = tuple((parse_integer, tag(","), parse_integer))(input);
+    match r {
+        Ok((s,val)) => IResult::Ok((s, Pos::new(val.0, val.2))),
+        Err(s) => IResult::Err(s),
+    }
+}
+
+pub fn parse_paths(input: &str) -> IResult<&str, Vec<Vec<Pos>>> {
+    match many0(
+        tuple(
+            (
+                separated_list0(tag(" -> "), parse_tuple), 
+                tag("\n")
+            )
+        )
+    )(input) {
+        Ok((str, val)) => IResult::Ok((str, val.into_iter().map(|v| v.0).collect())),
+        Err(e) => IResult::Err(e),
+    }
+}
+
+pub fn parse_indata(input: &str) -> Result<Vec<Vec<Pos>>, ParseError> {
+    let src = Source::new(Day14::DAY, input);
+    let paths = match parse_paths(input) {
+        Ok(("", paths)) => paths,
+        Ok((rest, _)) => return Err(src.error(rest, "Expected 'x,y -> x,y ...' ending with a newline")),
+        Err(e) => return Err(src.nom_error(e, "x,y -> x,y ...")),
+    };
+    for (line, path) in input.split('\n').zip(&paths) {
+        if path.windows(2).any(|ss| ss[0].x != ss[1].x && ss[0].y != ss[1].y) {
+            return Err(src.error(line, "Rock paths must be horizontal or vertical"));
+        }
+    }
+    Ok(paths)
+}
+
+
+
+
+pub fn draw_cave(cave: &Cave) -> Vec<String> {
+    let mut res = Vec::new();
+    let bounds = cave.occupied().fold(Bounds2::new(START), |b, p| b.extend(p));
+
+    for y in bounds.min.y..=bounds.max.y+3 {
+        let mut line = String::from("");
+        for x in bounds.min.x..=bounds.max.x {
+            line.push(match cave.get(&Pos::new(x,y)) {
+                Some(i) if *i == Content::Rock => '#',
+                Some(i) if *i == Content::Sand => 'o',
+                _ => ' ',
+            });
+        }
+        res.push(line);
+    }
+    res
+}
+
+
+// Rock in gray and sand in yellow, with the floor below.
+pub fn picture(cave: &Cave) -> Image {
+    let mut image = cave.data.map(|c| match c {
+        Some(Content::Rock) => [150, 150, 150],
+        Some(Content::Sand) => [230, 200, 80],
+        None => [20, 20, 30],
+    });
+    if let Some(row) = (cave.y_max + 1).try_into().ok().filter(|r| *r < image.rows()) {
+        for col in 0..image.cols() {
+            image[(row, col)] = [120, 80, 40];
+        }
+    }
+    image
+}
+
+#[derive(PartialEq)]
+pub enum SearchResult {
+    Pos(Pos),
+    Done,
+}
+pub fn find_resting_pos(cave: &Cave, pos: &Pos) -> SearchResult {
+    let mut y = pos.y;
+    loop {
+        match cave.get(&Pos::new(pos.x, y+1)) {
+            None => {},
+            _ => {
+                let left = Pos::new(pos.x-1, y+1);
+                if cave.get(&left).is_none() {
+                    match find_resting_pos(cave, &left) {
+                        SearchResult::Pos(left_pos) => { return SearchResult::Pos(left_pos); },
+                        SearchResult::Done => { return SearchResult::Done; },
+                    }
+                } 
+                let right = Pos::new(pos.x+1, y+1);
+                if cave.get(&right).is_none() {
+                    match find_resting_pos(cave, &right) {
+                        SearchResult::Pos(right_pos) => { return SearchResult::Pos(right_pos); },
+                        SearchResult::Done => { return SearchResult::Done; },
+                    }
+                }
+                return SearchResult::Pos(Pos::new(pos.x, y));
+            }
+        }
+        if y > cave.y_max {
+            return SearchResult::Done;
+        }
+        y += 1;
+    }    
+}
+
+pub fn drop_sand(cave: &mut Cave) -> SearchResult {
+
+    let p = find_resting_pos(cave, &START);
+    match &p {
+        &SearchResult::Pos(p) => { 
+            cave.insert(p, Content::Sand); 
+            if p == START {
+                SearchResult::Done
+            } else {
+                SearchResult::Pos(p)
+            }
+        },
+        _ => p,
+    }
+}
+
+pub fn count_sand(cave: &mut Cave) -> usize {
+    let mut count = 1;
+    while drop_sand(cave) != SearchResult::Done {
+        count += 1;
+    }
+    count
+}
+
+// Sand dropping one grain per step until it blocks START.
+pub struct SandScene {
+    pub cave: Cave,
+    pub count: usize,
+    last: Option<Pos>,
+}
+
+impl SandScene {
+    pub fn new(cave: Cave) -> Self {
+        Self { cave, count: 0, last: None }
+    }
+}
+
+impl Scene for SandScene {
+    fn step(&mut self) -> bool {
+        if self.last == Some(START) {
+            return false;
+        }
+        self.count += 1;
+        self.last = match find_resting_pos(&self.cave, &START) {
+            SearchResult::Pos(p) => {
+                self.cave.insert(p, Content::Sand);
+                Some(p)
+            }
+            SearchResult::Done => Some(START),
+        };
+        true
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        for p in self.cave.occupied() {
+            canvas.set(p, if self.cave.get(&p) == Some(&Content::Rock) { '#' } else { 'o' });
+        }
+        let bounds = self.cave.occupied().fold(Bounds2::new(START), |b, p| b.extend(p));
+        for x in bounds.min.x..=bounds.max.x {
+            canvas.set(Pos::new(x, self.cave.y_max + 2), '=');
+        }
+        if self.last != Some(START) {
+            canvas.set(START, '+');
+        }
+        canvas.focus(self.last.unwrap_or(START));
+    }
+
+    fn status(&self) -> String {
+        format!("{} units of sand", self.count)
+    }
+
+    fn steps_per_frame(&self) -> usize {
+        20
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = Vec<Vec<Pos>>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        count_sand(&mut Cave::from(input)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_DATA: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+
+    #[test]
+    fn test_part1() {
+        let res = parse_indata(TEST_DATA).unwrap();
+        assert_eq!(2, res.len());
+        assert_eq!(vec!(Pos::new(498,4),Pos::new(498,6),Pos::new(496,6)), res[0]);
+        assert_eq!(vec!(Pos::new(503,4),Pos::new(502,4),Pos::new(502,9),Pos::new(494,9)), res[1]);
+
+        let cave = Cave::from(&res);
+        assert_eq!(20, cave.occupied().count());
+
+        draw_cave(&cave);
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut cave = Cave::from(&parse_indata(TEST_DATA).unwrap());
+        assert_eq!(93, count_sand(&mut cave));
+
+        let mut scene = SandScene::new(Cave::from(&parse_indata(TEST_DATA).unwrap()));
+        while scene.step() {}
+        assert_eq!(93, scene.count);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("498,4 -> 498,6\n503,4 -> 502,x\n").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+
+        let err = parse_indata("498,4 -> 498,6\n503,4 -> 502,5\n").unwrap_err();
+        assert_eq!("503,4 -> 502,5", err.text);
+        assert_eq!("Rock paths must be horizontal or vertical", err.message);
+    }
+}
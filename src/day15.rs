@@ -0,0 +1,292 @@
+use std::ops::RangeInclusive;
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::{
+    error::{ParseError, Source},
+    grid::Grid,
+    image::{Image, Palette, BLACK, WHITE},
+    point::{Bounds2, Point2},
+    solution::{option_value, unknown_option, Answer, Solution},
+};
+
+pub type Pos = Point2<i64>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SensorData {
+    pub sensor: Pos,
+    pub beacon: Pos,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExclusionZone {
+    pub bounds: Bounds2<i64>,
+    pub sensors: Vec<SensorData>,
+}
+
+pub fn parse_indata(indata: &str) -> Result<ExclusionZone, ParseError> {
+    let src = Source::new(Day15::DAY, indata);
+    let re = Regex::new(r"^Sensor at x=([-]?\d+), y=([-]?\d+): closest beacon is at x=([-]?\d+), y=([-]?\d+)$").unwrap();
+    let sensors: Vec<SensorData> = src.lines()
+        .map(|l| {
+            let cap = src.captures(&re, l, "Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>")?;
+            Ok(SensorData {
+                sensor: Pos {x: src.number(&cap[1])?, y: src.number(&cap[2])?},
+                beacon: Pos {x: src.number(&cap[3])?, y: src.number(&cap[4])?},
+         })
+        })
+        .collect::<Result<_, ParseError>>()?;
+    if sensors.is_empty() {
+        return Err(src.error(indata, "No sensors in input"));
+    }
+    let bounds = Bounds2::from_points(sensors.iter().flat_map(|sd| [sd.sensor, sd.beacon])).unwrap();
+    Ok(ExclusionZone { bounds, sensors })
+}
+
+pub fn calc_ranges_for_line(y: i64, ez: &ExclusionZone) -> (Vec<RangeInclusive<i64>>, Vec<i64>) {
+    let mut ranges = Vec::new();
+    for s in &ez.sensors {
+        let dist_to_beacon = s.sensor.manhattan(s.beacon);
+        let dy = (y - s.sensor.y).abs();
+        if dy <= dist_to_beacon {
+            let dx = dist_to_beacon - dy;
+            ranges.push(s.sensor.x-dx..=s.sensor.x + dx);
+        }
+    }
+    ranges.sort_by_key(|r| *r.start());
+    let coalesced_ranges = ranges.into_iter().coalesce(|a, b| {
+        if b.start() - 1 <= *a.end() {
+            if b.end() > a.end() {
+                Ok(*a.start()..=*b.end())
+            } else {
+                Ok(a)
+            }
+        } else {
+            Err((a, b))
+        }
+    }).collect();
+    let beacon_xs = ez.sensors.iter().map(|sd| &sd.beacon).filter(|p| p.y == y).map(|p| p.x).collect();
+    (coalesced_ranges, beacon_xs)
+}
+
+pub fn count_excluded_in_ranges(ranges: &Vec<RangeInclusive<i64>>, beacons: &[i64], left: i64, right: i64) -> i64 {
+    let mut sum = 0;
+    for x in left..=right {
+        if !beacons.contains(&x) {
+            let mut excluded = false;
+            for r in ranges {
+                if x >= *r.start() && x <= *r.end() {
+                    excluded = true;
+                    break;
+                }
+            }
+            if excluded {
+                sum += 1;
+            }
+        }
+    }
+    sum
+}
+
+pub fn calc_candidate_beacon_positions(range: RangeInclusive<i64>, ez: &ExclusionZone) -> Vec<(i64,i64,i64)>{
+    let mut candidates: Vec<(i64,i64,i64)> = Vec::new();
+    for line in range {
+        let (ranges, _) = calc_ranges_for_line(line, ez);
+        if ranges.len() > 1 {
+            let x = *ranges[1].start() - 1;
+            candidates.push((x, line, x * 4000000 + line));
+
+        }
+    }
+    candidates
+}
+
+pub fn count_excluded_on_line(y: i64, ez: &ExclusionZone) -> i64 {
+    let (ranges, beacons) = calc_ranges_for_line(y, ez);
+    if ranges.is_empty() {
+        return 0;
+    }
+    let xspan = ranges.iter()
+        .fold(ranges[0].clone(), |a,x| {
+            let xmin: i64 = *a.start().min(x.start());
+            let xmax: i64 = *a.end().max(x.end());
+            xmin..=xmax
+        });
+    count_excluded_in_ranges(&ranges, &beacons, *xspan.start(), *xspan.end())
+}
+
+// The area from 0 to `max_coord` shrunk to `size` by `size` pixels, coloured by how many
+// sensors cover each pixel. Sensors are white, beacons cyan and the distress beacon red.
+pub fn picture(ez: &ExclusionZone, max_coord: i64, size: usize) -> Image {
+    let size = size.max(2);
+    let to_coord = |pixel: usize| pixel as i64 * max_coord / (size - 1) as i64;
+    let ranges: Vec<(Pos, i64)> = ez.sensors.iter().map(|sd| (sd.sensor, sd.sensor.manhattan(sd.beacon))).collect();
+    let mut image = Grid::new(size, size, BLACK);
+    for (row, col) in image.positions().collect::<Vec<_>>() {
+        let p = Pos::new(to_coord(col), to_coord(row));
+        let covered = ranges.iter().filter(|(sensor, range)| sensor.manhattan(p) <= *range).count() as i64;
+        image[(row, col)] = if covered == 0 { BLACK } else { Palette::HEAT.value(covered, 0, ranges.len() as i64) };
+    }
+    let mut mark = |p: Pos, colour| {
+        if (0..=max_coord).contains(&p.x) && (0..=max_coord).contains(&p.y) {
+            let to_pixel = |c: i64| (c * (size - 1) as i64 / max_coord.max(1)) as usize;
+            image[(to_pixel(p.y), to_pixel(p.x))] = colour;
+        }
+    };
+    for sd in &ez.sensors {
+        mark(sd.sensor, WHITE);
+        mark(sd.beacon, [0, 220, 220]);
+    }
+    if let Some((x, y, _)) = calc_candidate_beacon_positions(0..=max_coord, ez).first() {
+        mark(Pos::new(*x, *y), [255, 0, 0]);
+    }
+    image
+}
+
+pub struct Day15 {
+    pub row: i64,
+    pub max_coord: i64,
+}
+
+impl Default for Day15 {
+    fn default() -> Self {
+        Self { row: 2000000, max_coord: 4000000 }
+    }
+}
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Input = ExclusionZone;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        count_excluded_on_line(self.row, input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        match calc_candidate_beacon_positions(0..=self.max_coord, input).first() {
+            Some(candidate) => candidate.2.into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn options(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("row", self.row.to_string()),
+            ("max-coord", self.max_coord.to_string()),
+        ]
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "row" => self.row = option_value(name, value)?,
+            "max-coord" => self.max_coord = option_value(name, value)?,
+            _ => return Err(unknown_option(name, &self.options())),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+    Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+    Sensor at x=9, y=16: closest beacon is at x=10, y=16
+    Sensor at x=13, y=2: closest beacon is at x=15, y=3
+    Sensor at x=12, y=14: closest beacon is at x=10, y=16
+    Sensor at x=10, y=20: closest beacon is at x=10, y=16
+    Sensor at x=14, y=17: closest beacon is at x=10, y=16
+    Sensor at x=8, y=7: closest beacon is at x=2, y=10
+    Sensor at x=2, y=0: closest beacon is at x=2, y=10
+    Sensor at x=0, y=11: closest beacon is at x=2, y=10
+    Sensor at x=20, y=14: closest beacon is at x=25, y=17
+    Sensor at x=17, y=20: closest beacon is at x=21, y=22
+    Sensor at x=16, y=7: closest beacon is at x=15, y=3
+    Sensor at x=14, y=3: closest beacon is at x=15, y=3
+    Sensor at x=20, y=1: closest beacon is at x=15, y=3
+    "#
+    };
+
+    fn draw_ranges(ranges: &Vec<RangeInclusive<i64>>, beacons: &[i64], left: i64, right: i64) -> String {
+        let mut line = String::new();
+        for x in left..=right {
+            if beacons.contains(&x) {
+                line += "B";
+            } else {
+                let mut excluded = false;
+                for r in ranges {
+                    if x >= *r.start() && x <= *r.end() {
+                        excluded = true;
+                        break;
+                    }
+                }
+                    line += if excluded {"#"} else {"."};
+            }
+        }
+        line
+    }
+        
+    #[test]
+    fn test_part1() {
+        let exclusion_zone = parse_indata(TEST_DATA).unwrap();
+        assert_eq!(14, exclusion_zone.sensors.len());
+        assert_eq!(SensorData { 
+            sensor: Pos {x:2, y:18}, 
+            beacon: Pos {x:-2, y:15},
+        }, exclusion_zone.sensors[0]);
+        assert_eq!(SensorData { 
+            sensor: Pos {x:20, y:1}, 
+            beacon: Pos {x:15, y:3},
+        }, exclusion_zone.sensors[13]);
+        assert_eq!(Pos {x: -2, y: 0}, exclusion_zone.bounds.min);
+        assert_eq!(Pos {x: 25, y: 22}, exclusion_zone.bounds.max);
+
+        let (ranges, beacons) = calc_ranges_for_line(10, &exclusion_zone);
+        let line = draw_ranges(&ranges, &beacons, exclusion_zone.bounds.min.x, exclusion_zone.bounds.max.x);
+        assert_eq!("####B######################.", line);
+        assert_eq!(26, count_excluded_in_ranges(&ranges, &beacons, exclusion_zone.bounds.min.x, exclusion_zone.bounds.max.x));
+    }
+
+    #[test]
+    fn test_part2() {
+        let exclusion_zone = parse_indata(TEST_DATA).unwrap();
+        let pos_to_test = calc_candidate_beacon_positions(0..=20, &exclusion_zone);
+        assert_eq!(1, pos_to_test.len());
+        assert_eq!((14,11,56000011), pos_to_test[0]);
+    }
+
+    #[test]
+    fn test_options() {
+        let mut day = Day15::default();
+        day.set_option("row", "10").unwrap();
+        day.set_option("max-coord", "20").unwrap();
+        let exclusion_zone = day.parse(TEST_DATA).unwrap();
+        assert_eq!(Answer::Int(26), day.part1(&exclusion_zone));
+        assert_eq!(Answer::Int(56000011), day.part2(&exclusion_zone));
+        day.set_option("row", "100000000").unwrap();
+        assert_eq!(Answer::Int(0), day.part1(&exclusion_zone));
+    }
+
+    #[test]
+    fn test_row_out_of_reach() {
+        let exclusion_zone = parse_indata("Sensor at x=0, y=0: closest beacon is at x=1, y=0\n").unwrap();
+        assert_eq!(0, count_excluded_on_line(2000000, &exclusion_zone));
+        assert_eq!(2, count_excluded_on_line(0, &exclusion_zone));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata(&TEST_DATA.replace("x=9, y=16", "x=9 y=16")).unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+        assert_eq!("Sensor at x=9 y=16: closest beacon is at x=10, y=16", err.text);
+
+        let err = parse_indata("").unwrap_err();
+        assert_eq!("No sensors in input", err.message);
+    }
+}
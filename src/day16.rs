@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use regex::Regex;
+
+use crate::{
+    error::{ParseError, Source},
+    pathfinding::{bfs, dijkstra, Graph},
+    solution::{Answer, Solution},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Valve {
+    flow: i32,
+    connections: Vec<String>,
+}
+
+// Valves joined by tunnels, every tunnel takes one minute to walk.
+impl Graph for HashMap<String, Valve> {
+    type Node = String;
+
+    fn neighbours(&self, node: &String) -> Vec<(String, usize)> {
+        self[node].connections.iter().map(|name| (name.clone(), 1)).collect()
+    }
+}
+
+pub fn calculate_cost(from: &str, to: &str, valves: &HashMap<String, Valve>) -> i32 {
+    let to = String::from(to);
+    dijkstra(valves, String::from(from), Some(&to))
+        .distance(&to)
+        .map_or(i32::MAX, |d| d as i32)
+}
+
+// The distances from `start` and from every valve with a flow to all the other valves with a flow.
+pub fn calculate_distances(start: &String, valves: &HashMap<String, Valve>) -> HashMap<(String, String), i32> {
+    let flowing: Vec<&String> = valves.iter().filter(|(_, valve)| valve.flow > 0).map(|(name, _)| name).collect();
+    let mut res = HashMap::new();
+    for from in flowing.iter().copied().chain([start]) {
+        let search = bfs(valves, from.clone());
+        for to in flowing.iter().filter(|to| **to != from) {
+            if let Some(dist) = search.distance(to) {
+                res.insert((from.clone(), (*to).clone()), dist as i32);
+            }
+        }
+    }
+    res
+}
+
+pub fn parse_indata(indata: &str) -> Result<HashMap<String, Valve>, ParseError> {
+    let src = Source::new(Day16::DAY, indata);
+    let re = Regex::new(r"^Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? (\w+(?:, \w+)*)$").unwrap();
+    let mut valves = HashMap::new();
+    let mut connections = Vec::new();
+    for l in src.lines() {
+        let cap = src.captures(&re, l, "Valve <name> has flow rate=<rate>; tunnels lead to valves <name>, ...")?;
+        let names: Vec<&str> = cap.get(3).unwrap().as_str().split(',').map(|s| s.trim()).collect();
+        connections.extend(names.iter().copied());
+        let valve = Valve {
+            flow: src.number(&cap[2])?,
+            connections: names.into_iter().map(String::from).collect(),
+        };
+        if valves.insert(String::from(&cap[1]), valve).is_some() {
+            return Err(src.error(l, format!("Valve {} is defined twice", &cap[1])));
+        }
+    }
+    if let Some(name) = connections.into_iter().find(|name| !valves.contains_key(*name)) {
+        return Err(src.error(name, format!("There is no valve {name}")));
+    }
+    Ok(valves)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = HashMap<String, Valve>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+    Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+    Valve BB has flow rate=13; tunnels lead to valves CC, AA
+    Valve CC has flow rate=2; tunnels lead to valves DD, BB
+    Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+    Valve EE has flow rate=3; tunnels lead to valves FF, DD
+    Valve FF has flow rate=0; tunnels lead to valves EE, GG
+    Valve GG has flow rate=0; tunnels lead to valves FF, HH
+    Valve HH has flow rate=22; tunnel leads to valve GG
+    Valve II has flow rate=0; tunnels lead to valves AA, JJ
+    Valve JJ has flow rate=21; tunnel leads to valve II
+    "#
+    };
+
+       
+    #[test]
+    fn test_part1() {
+        let valves = parse_indata(TEST_DATA).unwrap();
+        assert_eq!(10, valves.len());
+        assert_eq!(&Valve { 
+            flow: 0,
+            connections: vec![String::from("DD"), String::from("II"), String::from("BB")],
+        }, valves.get(&String::from("AA")).unwrap());
+        assert_eq!(&Valve { 
+            flow: 21,
+            connections: vec![String::from("II")],
+        }, valves.get(&String::from("JJ")).unwrap());
+    }
+
+    #[test]
+    fn test_distances() {
+        let valves = parse_indata(TEST_DATA).unwrap();
+        let aa = String::from("AA");
+        assert_eq!(5, calculate_cost(&aa, &String::from("HH"), &valves));
+        let dists = calculate_distances(&aa, &valves);
+        assert_eq!(6 * 6, dists.len());
+        assert_eq!(Some(&5), dists.get(&(aa, String::from("HH"))));
+        assert_eq!(Some(&7), dists.get(&(String::from("HH"), String::from("JJ"))));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("Valve AA has flow rate=0; tunnels lead to valves BB\n").unwrap_err();
+        assert_eq!((1, 50), (err.line, err.column));
+        assert_eq!("There is no valve BB", err.message);
+
+        let err = parse_indata("Valve AA has flow rate=x; tunnels lead to valves AA\n").unwrap_err();
+        assert_eq!((1, 1), (err.line, err.column));
+    }
+}
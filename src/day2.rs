@@ -0,0 +1,436 @@
+use crate::{
+    error::{ParseError, Source},
+    solution::{unknown_option, Answer, Solution},
+};
+use std::{fs, path::Path};
+use toml::Value;
+
+// The game of the puzzle. A game definition has a table per move with its score, the
+// symbols for it in the two columns of the strategy guide and the moves it beats, and
+// the outcomes with their scores and the symbols for them in the second column.
+pub const ROCK_PAPER_SCISSORS: &str = r#"
+[outcomes]
+lose = { symbol = "X", score = 0 }
+draw = { symbol = "Y", score = 3 }
+win = { symbol = "Z", score = 6 }
+
+[[moves]]
+name = "rock"
+score = 1
+opponent = "A"
+player = "X"
+beats = ["scissors"]
+
+[[moves]]
+name = "paper"
+score = 2
+opponent = "B"
+player = "Y"
+beats = ["rock"]
+
+[[moves]]
+name = "scissors"
+score = 3
+opponent = "C"
+player = "Z"
+beats = ["paper"]
+"#;
+
+pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: &str = r#"
+[outcomes]
+lose = { symbol = "X", score = 0 }
+draw = { symbol = "Y", score = 3 }
+win = { symbol = "Z", score = 6 }
+
+[[moves]]
+name = "rock"
+score = 1
+opponent = "A"
+player = "V"
+beats = ["scissors", "lizard"]
+
+[[moves]]
+name = "paper"
+score = 2
+opponent = "B"
+player = "W"
+beats = ["rock", "spock"]
+
+[[moves]]
+name = "scissors"
+score = 3
+opponent = "C"
+player = "X"
+beats = ["paper", "lizard"]
+
+[[moves]]
+name = "lizard"
+score = 4
+opponent = "D"
+player = "Y"
+beats = ["spock", "paper"]
+
+[[moves]]
+name = "spock"
+score = 5
+opponent = "E"
+player = "Z"
+beats = ["scissors", "rock"]
+"#;
+
+// The games known by name, others are read from a file.
+pub const GAMES: [(&str, &str); 2] = [
+    ("rock-paper-scissors", ROCK_PAPER_SCISSORS),
+    ("rock-paper-scissors-lizard-spock", ROCK_PAPER_SCISSORS_LIZARD_SPOCK),
+];
+
+// The symbols of a line of the strategy guide.
+pub type Round = (char, char);
+
+// A way to read a round as the moves of the opponent and the player.
+pub type Interpretation = fn(&Game, &Round) -> Option<(Choice, Choice)>;
+
+// A move of a game, by its index in Game::moves.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, PartialOrd, Ord)]
+pub struct Choice(pub usize);
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Lose => "lose",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Move {
+    pub name: String,
+    pub score: i32,
+    pub opponent: char,
+    pub player: char,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Game {
+    pub moves: Vec<Move>,
+    // beats[a][b] when move a beats move b.
+    beats: Vec<Vec<bool>>,
+    // The symbol and score of each outcome, in the order of Outcome::ALL.
+    pub outcomes: [(char, i32); 3],
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::parse(ROCK_PAPER_SCISSORS).unwrap()
+    }
+}
+
+fn symbol(value: &Value, what: &str) -> Result<char, String> {
+    let s = value.as_str().ok_or(format!("{what} must be a string"))?;
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if !ch.is_whitespace() => Ok(ch),
+        _ => Err(format!("{what} must be a single character, found \"{s}\"")),
+    }
+}
+
+fn field<'a>(table: &'a Value, key: &str, what: &str) -> Result<&'a Value, String> {
+    table.get(key).ok_or(format!("{what} has no {key}"))
+}
+
+fn read_score(table: &Value, what: &str) -> Result<i32, String> {
+    let score = field(table, "score", what)?.as_integer().ok_or(format!("The score of {what} must be an integer"))?;
+    i32::try_from(score).map_err(|_| format!("The score of {what} is too large"))
+}
+
+impl Game {
+    // Reads a game definition, see ROCK_PAPER_SCISSORS. Every move has to beat another and
+    // be beaten by another, so that each outcome can be reached against every move, and
+    // a symbol stands for one move only.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let value: Value = toml::from_str(text).map_err(|e| e.to_string())?;
+        let tables = value.get("moves").and_then(|m| m.as_array()).ok_or("A game needs a [[moves]] list")?;
+        let mut moves = vec![];
+        for (ix, table) in tables.iter().enumerate() {
+            let what = format!("move {}", ix + 1);
+            let name = field(table, "name", &what)?.as_str().ok_or(format!("The name of {what} must be a string"))?;
+            let what = format!("move {name}");
+            if moves.iter().any(|m: &Move| m.name == name) {
+                return Err(format!("There are two moves named {name}"));
+            }
+            moves.push(Move {
+                name: String::from(name),
+                score: read_score(table, &what)?,
+                opponent: symbol(field(table, "opponent", &what)?, &format!("The opponent symbol of {what}"))?,
+                player: symbol(field(table, "player", &what)?, &format!("The player symbol of {what}"))?,
+            });
+        }
+        if moves.len() < 3 {
+            return Err(String::from("A game needs at least three moves"));
+        }
+        for (ix, a) in moves.iter().enumerate() {
+            if let Some(b) = moves[ix + 1..].iter().find(|b| a.opponent == b.opponent || a.player == b.player) {
+                return Err(format!("The moves {} and {} have the same symbol", a.name, b.name));
+            }
+        }
+
+        let mut beats = vec![vec![false; moves.len()]; moves.len()];
+        for (a, table) in tables.iter().enumerate() {
+            let name = &moves[a].name;
+            let beaten = field(table, "beats", &format!("move {name}"))?.as_array().ok_or(format!("The beats of move {name} must be a list"))?;
+            for other in beaten {
+                let other = other.as_str().ok_or(format!("The beats of move {name} must be move names"))?;
+                let b = moves.iter().position(|m| m.name == other).ok_or(format!("Move {name} beats unknown move {other}"))?;
+                if a == b {
+                    return Err(format!("Move {name} can not beat itself"));
+                }
+                beats[a][b] = true;
+            }
+        }
+        for (a, m) in moves.iter().enumerate() {
+            if let Some(b) = (0..moves.len()).find(|b| beats[a][*b] && beats[*b][a]) {
+                return Err(format!("The moves {} and {} beat each other", m.name, moves[b].name));
+            }
+            if !beats[a].contains(&true) || !beats.iter().any(|row| row[a]) {
+                return Err(format!("Move {} has to beat a move and be beaten by one", m.name));
+            }
+        }
+
+        let table = value.get("outcomes").ok_or("A game needs an [outcomes] table")?;
+        let mut outcomes = [(' ', 0); 3];
+        for (ix, outcome) in Outcome::ALL.iter().enumerate() {
+            let what = format!("outcome {}", outcome.name());
+            let entry = field(table, outcome.name(), "[outcomes]")?;
+            outcomes[ix] = (symbol(field(entry, "symbol", &what)?, &format!("The symbol of {what}"))?, read_score(entry, &what)?);
+        }
+        if outcomes.iter().enumerate().any(|(ix, (s, _))| outcomes[ix + 1..].iter().any(|(t, _)| s == t)) {
+            return Err(String::from("The outcomes have to have different symbols"));
+        }
+        Ok(Self { moves, beats, outcomes })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    // A game from GAMES by name, or from the file at `name`.
+    pub fn named(name: &str) -> Result<Self, String> {
+        match GAMES.iter().find(|(n, _)| *n == name) {
+            Some((_, text)) => Self::parse(text),
+            None => Self::load(Path::new(name)),
+        }
+    }
+
+    pub fn opponent(&self, symbol: char) -> Option<Choice> {
+        self.moves.iter().position(|m| m.opponent == symbol).map(Choice)
+    }
+
+    pub fn player(&self, symbol: char) -> Option<Choice> {
+        self.moves.iter().position(|m| m.player == symbol).map(Choice)
+    }
+
+    pub fn outcome_of(&self, symbol: char) -> Option<Outcome> {
+        Outcome::ALL.iter().zip(&self.outcomes).find(|(_, (s, _))| *s == symbol).map(|(o, _)| *o)
+    }
+
+    pub fn value(&self, c: Choice) -> i32 {
+        self.moves[c.0].score
+    }
+
+    // The outcome for the player of `player` against `opponent`. Moves that do not beat
+    // each other draw.
+    pub fn outcome(&self, opponent: Choice, player: Choice) -> Outcome {
+        if self.beats[player.0][opponent.0] {
+            Outcome::Win
+        } else if self.beats[opponent.0][player.0] {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> i32 {
+        self.outcomes[outcome as usize].1
+    }
+
+    // The move giving `outcome` against `opponent`, the one with the highest score when
+    // there are several.
+    pub fn respond(&self, opponent: Choice, outcome: Outcome) -> Choice {
+        (0..self.moves.len())
+            .map(Choice)
+            .filter(|c| self.outcome(opponent, *c) == outcome)
+            .max_by_key(|c| (self.value(*c), std::cmp::Reverse(*c)))
+            .unwrap()
+    }
+
+    // The symbols of the columns as in "<A|B|C> <X|Y|Z>".
+    fn columns(&self) -> String {
+        let mut second: Vec<char> = self.moves.iter().map(|m| m.player).chain(self.outcomes.iter().map(|(s, _)| *s)).collect();
+        second.sort_unstable();
+        second.dedup();
+        let join = |symbols: Vec<char>| symbols.iter().map(char::to_string).collect::<Vec<_>>().join("|");
+        format!("<{}> <{}>", join(self.moves.iter().map(|m| m.opponent).collect()), join(second))
+    }
+}
+
+// The rounds of the strategy guide as symbols, the second column is read as a move of the
+// player or as an outcome depending on the part.
+pub fn parse_indata(game: &Game, indata: &str) -> Result<Vec<Round>, ParseError> {
+    let src = Source::new(Day2::DAY, indata);
+    src.lines()
+        .map(|l| {
+            let mut chars = l.chars();
+            match (chars.next(), chars.next(), chars.next(), chars.next()) {
+                (Some(a), Some(' '), Some(b), None) => {
+                    if game.opponent(a).is_none() {
+                        return Err(src.error(l, format!("Unknown choice '{a}'")));
+                    }
+                    if game.player(b).is_none() && game.outcome_of(b).is_none() {
+                        return Err(src.error(&l[a.len_utf8() + 1..], format!("Unknown choice '{b}'")));
+                    }
+                    Ok((a, b))
+                }
+                _ => Err(src.error(l, format!("Expected '{}'", game.columns()))),
+            }
+        })
+        .collect()
+}
+
+pub fn score(game: &Game, c: &(Choice, Choice)) -> i32 {
+    game.value(c.1) + game.outcome_score(game.outcome(c.0, c.1))
+}
+
+// The second column as the move of the player, None for an outcome symbol that is not
+// also a move.
+pub fn moves(game: &Game, round: &Round) -> Option<(Choice, Choice)> {
+    Some((game.opponent(round.0)?, game.player(round.1)?))
+}
+
+// The second column as the outcome the player has to get.
+pub fn calc_choice(game: &Game, round: &Round) -> Option<(Choice, Choice)> {
+    let opponent = game.opponent(round.0)?;
+    Some((opponent, game.respond(opponent, game.outcome_of(round.1)?)))
+}
+
+// None when `f` can not read a round.
+pub fn process(game: &Game, strategy: &[Round], f: Interpretation) -> Option<i32> {
+    strategy
+        .iter()
+        .map(|r| f(game, r))
+        .map(|t| t.map(|t| score(game, &t)))
+        .sum::<Option<i32>>()
+}
+
+pub struct Day2 {
+    // The name the game was given by, see Game::named.
+    pub name: String,
+    pub game: Game,
+}
+
+impl Default for Day2 {
+    fn default() -> Self {
+        Self { name: String::from(GAMES[0].0), game: Game::default() }
+    }
+}
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Input = Vec<Round>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(&self.game, indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        process(&self.game, input, moves).map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        process(&self.game, input, calc_choice).map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn options(&self) -> Vec<(&'static str, String)> {
+        vec![("game", self.name.clone())]
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "game" => {
+                self.game = Game::named(value)?;
+                self.name = String::from(value);
+            }
+            _ => return Err(unknown_option(name, &self.options())),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_example() {
+        let test_data = indoc! {r#"
+        A Y
+        B X
+        C Z
+        "#
+        };
+
+        let game = Game::default();
+        let strategy = parse_indata(&game, test_data).unwrap();
+        assert_eq!(3, strategy.len());
+
+        assert_eq!(Some(15), process(&game, &strategy, moves));
+        assert_eq!(Some(12), process(&game, &strategy, calc_choice));
+        assert_eq!("Expected '<A|B|C> <X|Y|Z>'", parse_indata(&game, "A  Y\n").unwrap_err().message);
+        assert!(parse_indata(&game, "D Y\n").is_err());
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let game = Game::named("rock-paper-scissors-lizard-spock").unwrap();
+        let (rock, lizard, spock) = (Choice(0), Choice(3), Choice(4));
+        assert_eq!(Outcome::Win, game.outcome(lizard, rock));
+        assert_eq!(Outcome::Lose, game.outcome(spock, rock));
+        assert_eq!(Outcome::Draw, game.outcome(spock, spock));
+        // Scissors and lizard both lose to rock, lizard scores more.
+        assert_eq!(lizard, game.respond(rock, Outcome::Lose));
+        assert_eq!(lizard, game.respond(spock, Outcome::Win));
+
+        let strategy = parse_indata(&game, "A V\nE Y\nD W\n").unwrap();
+        // 1 + 3, 4 + 6 and 2 + 0.
+        assert_eq!(Some(16), process(&game, &strategy, moves));
+        // The player symbols V and W are no outcomes.
+        assert_eq!(None, process(&game, &strategy, calc_choice));
+        assert_eq!(Some(5 + 6), process(&game, &[('A', 'Z')], calc_choice));
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        let error = |text: String| Game::parse(&text).unwrap_err();
+        assert_eq!("Move paper has to beat a move and be beaten by one", error(ROCK_PAPER_SCISSORS.replace("beats = [\"paper\"]", "beats = []")));
+        assert_eq!("The moves rock and paper have the same symbol", error(ROCK_PAPER_SCISSORS.replace("\"B\"", "\"A\"")));
+        assert_eq!("Move paper beats unknown move stone", error(ROCK_PAPER_SCISSORS.replace("beats = [\"rock\"]", "beats = [\"stone\"]")));
+        assert!(Game::named("no-such-game.toml").is_err());
+
+        let mut day = Day2::default();
+        assert!(day.set_option("game", "rock-paper-scissors-lizard-spock").is_ok());
+        assert_eq!(vec![("game", String::from("rock-paper-scissors-lizard-spock"))], Solution::options(&day));
+        assert_eq!(5, day.game.moves.len());
+    }
+}
@@ -0,0 +1,120 @@
+use itertools::Itertools;
+use std::collections::HashSet;
+
+use crate::{
+    error::{ParseError, Source},
+    solution::{Answer, Solution},
+};
+
+pub fn to_priority(item: u8) -> i32 {
+    match item {
+        b'a'..=b'z' => (item - b'a' + 1) as i32,
+        b'A'..=b'Z' => (item - b'A' + 27) as i32,
+        _ => 0,
+    }
+}
+
+pub type InData = Vec<(HashSet<i32>, HashSet<i32>)>;
+
+pub fn parse_indata(indata: &str) -> Result<InData, ParseError> {
+    let src = Source::new(Day3::DAY, indata);
+    src.lines()
+        .map(|l| {
+            if let Some(ix) = l.find(|c: char| !c.is_ascii_alphabetic()) {
+                return Err(src.error(&l[ix..], "Items must be letters a-z or A-Z"));
+            }
+            if l.len() % 2 != 0 {
+                return Err(src.error(l, "Rucksack has an odd number of items"));
+            }
+            let (left, right) = l.split_at(l.len() / 2);
+            let (left, right) = (
+                left.bytes().map(to_priority).collect::<HashSet<_>>(),
+                right.bytes().map(to_priority).collect::<HashSet<_>>(),
+            );
+            if left.is_disjoint(&right) {
+                return Err(src.error(l, "No item is in both compartments"));
+            }
+            Ok((left, right))
+        })
+        .collect()
+}
+
+pub fn process1(data: &[(HashSet<i32>, HashSet<i32>)]) -> i32 {
+    data.iter()
+        .filter_map(|(l, r)| l.intersection(r).next())
+        .sum()
+}
+
+// None when a group of three has no badge in common.
+pub fn process2(data: &[(HashSet<i32>, HashSet<i32>)]) -> Option<i32> {
+    data.iter()
+        .map(|(l, r)| l | r)
+        .tuples()
+        .map(|(s1, s2, s3)| (&(&s1 & &s2) & &s3).into_iter().next())
+        .sum()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Input = InData;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        process1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        process2(input).map_or(Answer::Unsolved, Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+    vJrwpWtwJgWrhcsFMMfFFhFp
+    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+    PmmdzqPrVvPwwTWBwg
+    wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+    ttgJtRGJQctTZtZT
+    CrZsJsPPZsGzwwsLwLmpwMDw
+    "#
+    };
+    #[test]
+    fn test_part1() {
+        let data = parse_indata(TEST_DATA).unwrap();
+        let score = process1(&data);
+        assert_eq!(157, score);
+    }
+
+    #[test]
+    fn test_part2() {
+        let data = parse_indata(TEST_DATA).unwrap();
+        let score = process2(&data);
+        assert_eq!(Some(70), score);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("vJrwpWtwJgWrhcsFMMfFFhFp\nabcd\n").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+        assert_eq!("No item is in both compartments", err.message);
+
+        let err = parse_indata("vJrwpWtw1gWrhcsFMMfFFhFp\n").unwrap_err();
+        assert_eq!((1, 9), (err.line, err.column));
+    }
+
+    #[test]
+    fn test_no_badge() {
+        let data = parse_indata("aa\nbb\ncc\n").unwrap();
+        assert_eq!(6, process1(&data));
+        assert_eq!(None, process2(&data));
+    }
+}
@@ -0,0 +1,102 @@
+use regex::Regex;
+
+use crate::{
+    error::{ParseError, Source},
+    solution::{Answer, Solution},
+};
+
+pub fn parse_indata(indata: &str) -> Result<Vec<(i32, i32, i32, i32)>, ParseError> {
+    let src = Source::new(Day4::DAY, indata);
+    let re = Regex::new(r"^(\d+)-(\d+),(\d+)-(\d+)$").unwrap();
+    src.lines()
+        .map(|l| {
+            let cap = src.captures(&re, l, "a-b,c-d")?;
+            Ok((
+                src.number(&cap[1])?,
+                src.number(&cap[2])?,
+                src.number(&cap[3])?,
+                src.number(&cap[4])?,
+            ))
+        })
+        .collect()
+}
+
+pub fn complete_overlap(d: &(i32, i32, i32, i32)) -> bool {
+    match d {
+        (a, b, c, d) if a >= c && b <= d => true,
+        (a, b, c, d) if c >= a && d <= b => true,
+        _ => false,
+    }
+}
+
+pub fn some_overlap(d: &(i32, i32, i32, i32)) -> bool {
+    match d {
+        (a, _b, c, d) if a >= c && a <= d => true,
+        (_a, b, c, d) if b >= c && b <= d => true,
+        (a, b, c, d) if a <= c && b >= d => true,
+        _ => false,
+    }
+}
+
+pub fn process(data: &[(i32, i32, i32, i32)], f: fn(&(i32, i32, i32, i32)) -> bool) -> usize {
+    data.iter().filter(|d| f(d)).count()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Input = Vec<(i32, i32, i32, i32)>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        process(input, complete_overlap).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        process(input, some_overlap).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+    2-4,6-8
+    2-3,4-5
+    5-7,7-9
+    2-8,3-7
+    6-6,4-6
+    2-6,4-8
+    "#
+    };
+
+    #[test]
+    fn test_part1() {
+        let data = parse_indata(TEST_DATA).unwrap();
+        let score = process(&data, complete_overlap);
+        assert_eq!(2, score);
+    }
+
+    #[test]
+    fn test_part2() {
+        let data = parse_indata(TEST_DATA).unwrap();
+        let score = process(&data, some_overlap);
+        assert_eq!(4, score);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("2-4,6-8\n2-3,4_5\n").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+        assert_eq!("2-3,4_5", err.text);
+
+        let err = parse_indata("2-4,6-99999999999\n").unwrap_err();
+        assert_eq!((1, 7), (err.line, err.column));
+    }
+}
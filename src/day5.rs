@@ -0,0 +1,200 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{anychar, digit1},
+    combinator::{map, map_res},
+    multi::separated_list1,
+    sequence::delimited,
+    IResult, Parser,
+};
+
+use crate::{
+    error::{ParseError, Source},
+    solution::{Answer, Solution},
+};
+
+pub fn parse_crates(input: &str) -> IResult<&str, Vec<Option<char>>> {
+    separated_list1(
+        tag(" "),
+        map(delimited(tag("["), anychar, tag("]")), Some).or(map(tag("   "), |_t: &str| None)),
+    )(input)
+}
+
+pub fn parse_number(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, |num: &str| num.parse())(input)
+}
+
+pub fn parse_command(input: &str) -> IResult<&str, (usize, usize, usize)> {
+    let (input, _) = tag("move ")(input)?;
+    let (input, nr) = parse_number(input)?;
+    let (input, _) = tag(" from ")(input)?;
+    let (input, from) = parse_number(input)?;
+    let (input, _) = tag(" to ")(input)?;
+    let (input, to) = parse_number(input)?;
+
+    Ok((input, (nr, from, to)))
+}
+pub type InData = (Vec<Vec<char>>, Vec<(usize, usize, usize)>);
+
+pub fn parse_indata(indata: &str) -> Result<InData, ParseError> {
+    let src = Source::new(Day5::DAY, indata);
+    let mut it = src.lines();
+    let mut stacks = vec![];
+    let numbers = loop {
+        let line = it.next().ok_or_else(|| src.error(&indata[indata.len()..], "Missing stack numbers"))?;
+        let row_result = match parse_crates(line) {
+            Ok(("", row_result)) => row_result,
+            Ok((rest, _)) => return Err(src.error(rest, "Expected '[X]' or '   '")),
+            Err(_) => break line,
+        };
+        // Rows lose their empty stacks on the right when trailing spaces are trimmed.
+        if row_result.len() > stacks.len() {
+            stacks.resize(row_result.len(), vec![]);
+        }
+        for (ix, res) in row_result.into_iter().enumerate() {
+            if let Some(crte) = res {
+                stacks[ix].push(crte);
+            }
+        }
+    };
+
+    for (ix, nr) in numbers.split_whitespace().enumerate() {
+        if src.number::<usize>(nr)? != ix + 1 {
+            return Err(src.error(nr, format!("Expected stack number {}", ix + 1)));
+        }
+    }
+    if numbers.split_whitespace().count() < stacks.len() {
+        return Err(src.error(numbers, format!("Expected {} stack numbers", stacks.len())));
+    }
+    stacks.resize(numbers.split_whitespace().count(), vec![]);
+
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    let commands: Vec<(usize, usize, usize)> = it
+        .map(|input| {
+            let command = match parse_command(input) {
+                Ok(("", command)) => command,
+                Ok((rest, _)) => return Err(src.error(rest, "Unexpected text after command")),
+                Err(e) => return Err(src.nom_error(e, "move N from A to B")),
+            };
+            if !(1..=stacks.len()).contains(&command.1) || !(1..=stacks.len()).contains(&command.2) {
+                return Err(src.error(input, format!("Stacks are numbered 1 to {}", stacks.len())));
+            }
+            let (count, from, to) = command;
+            if count > heights[from - 1] {
+                return Err(src.error(input, format!("Stack {from} has only {} crates", heights[from - 1])));
+            }
+            heights[from - 1] -= count;
+            heights[to - 1] += count;
+            Ok(command)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((
+        stacks
+            .into_iter()
+            .map(|v| v.into_iter().rev().collect())
+            .collect(),
+        commands,
+    ))
+}
+
+// (nr, from, to), the answer leaves out stacks that end up empty.
+pub fn play_commands(stacks: &[Vec<char>], commands: &[(usize, usize, usize)]) -> String {
+    let mut ss = stacks.to_vec();
+    for cmd in commands.iter() {
+        for _ in 0..cmd.0 {
+            let cr = ss[cmd.1 - 1].pop().unwrap();
+            ss[cmd.2 - 1].push(cr);
+        }
+    }
+    ss.iter().filter_map(|s| s.last()).collect()
+}
+
+// As play_commands, with the crates moved all at once.
+pub fn play_commands2(stacks: &[Vec<char>], commands: &[(usize, usize, usize)]) -> String {
+    let mut ss = stacks.to_vec();
+    for cmd in commands.iter() {
+        let mut tmp = vec![];
+        for _ in 0..cmd.0 {
+            tmp.push(ss[cmd.1 - 1].pop().unwrap());
+        }
+        tmp.reverse();
+        for cc in tmp {
+            ss[cmd.2 - 1].push(cc);
+        }
+    }
+    ss.iter().filter_map(|s| s.last()).collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Input = InData;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        play_commands(&input.0, &input.1).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        play_commands2(&input.0, &input.1).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+        [D]    
+    [N] [C]    
+    [Z] [M] [P]
+    1   2   3 
+
+    move 1 from 2 to 1
+    move 3 from 1 to 3
+    move 2 from 2 to 1
+    move 1 from 1 to 2
+    "#
+    };
+
+    #[test]
+    fn test_part1() {
+        let (stacks, commands) = parse_indata(TEST_DATA).unwrap();
+        let result = play_commands(&stacks, &commands);
+        assert_eq!("CMZ", result);
+    }
+
+    #[test]
+    fn test_part2() {
+        let (stacks, commands) = parse_indata(TEST_DATA).unwrap();
+        let result = play_commands2(&stacks, &commands);
+        assert_eq!("MCD", result);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata(&TEST_DATA.replace("move 3", "mve 3")).unwrap_err();
+        assert_eq!((7, 1), (err.line, err.column));
+        assert_eq!("Expected 'move N from A to B'", err.message);
+
+        let err = parse_indata("[A] [B]\n 1   2 \n\nmove 1 from 1 to 3\n").unwrap_err();
+        assert_eq!((4, 1), (err.line, err.column));
+        assert_eq!("Stacks are numbered 1 to 2", err.message);
+
+        let err = parse_indata(&TEST_DATA.replace("move 3 from 1", "move 4 from 1")).unwrap_err();
+        assert_eq!((7, 1), (err.line, err.column));
+        assert_eq!("Stack 1 has only 3 crates", err.message);
+    }
+
+    #[test]
+    fn test_emptied_stack() {
+        let (stacks, commands) = parse_indata("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\n").unwrap();
+        assert_eq!("A", play_commands(&stacks, &commands));
+        assert_eq!("A", play_commands2(&stacks, &commands));
+    }
+}
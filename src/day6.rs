@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::{ParseError, Source},
+    solution::{Answer, Solution},
+};
+
+pub fn find_marker_index(input: &str, nr: usize) -> usize {
+    let ch: Vec<char> = input.chars().collect();
+    let mut index = nr;
+    for ws in ch.windows(nr) {
+        let s: HashSet<char> = ws.iter().copied().collect();
+        if s.len() == nr {
+            break;
+        }
+        index += &1;
+    }
+    index
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Input = String;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        let stream = indata.trim_end();
+        if let Some(ix) = stream.find(|c: char| !c.is_ascii_lowercase()) {
+            return Err(Source::new(Self::DAY, indata).error(&stream[ix..], "Expected letters a-z"));
+        }
+        Ok(String::from(stream))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        find_marker_index(input, 4).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        find_marker_index(input, 14).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let len: usize = 4;
+        assert_eq!(7, find_marker_index("mjqjpqmgbljsphdztnvjfqwrcgsmlb", len));
+        assert_eq!(5, find_marker_index("bvwbjplbgvbhsrlpgdmjqwftvncz", len));
+        assert_eq!(6, find_marker_index("nppdvjthqldpwncqszvftbrmjlhg", len));
+        assert_eq!(10, find_marker_index("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", len));
+        assert_eq!(11, find_marker_index("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", len));
+    }
+
+    #[test]
+    fn test_part2() {
+        let len: usize = 14;
+        assert_eq!(19, find_marker_index("mjqjpqmgbljsphdztnvjfqwrcgsmlb", len));
+        assert_eq!(23, find_marker_index("bvwbjplbgvbhsrlpgdmjqwftvncz", len));
+        assert_eq!(23, find_marker_index("nppdvjthqldpwncqszvftbrmjlhg", len));
+        assert_eq!(29, find_marker_index("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", len));
+        assert_eq!(26, find_marker_index("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", len));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day6.parse("mjqjpq mgbljsphdztnvjfqwrcgsmlb\n").unwrap_err();
+        assert_eq!((1, 7), (err.line, err.column));
+        assert_eq!("Expected letters a-z", err.message);
+    }
+}
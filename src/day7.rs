@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{ParseError, Source},
+    solution::{option_value, unknown_option, Answer, Solution},
+};
+
+pub fn parse_indata(input: &str) -> Result<HashMap<Vec<String>, usize>, ParseError> {
+    let src = Source::new(Day7::DAY, input);
+    let mut paths: Vec<&str> = Vec::new();
+    let mut sizes = HashMap::new();
+
+    for line in src.lines() {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some("$"), Some("cd"), Some(".."), None) => {
+                if paths.pop().is_none() {
+                    return Err(src.error(line, "Can not leave the root directory"));
+                }
+            }
+            (Some("$"), Some("cd"), Some(dir), None) => {
+                paths.push(dir);
+            }
+            (Some("$"), Some("ls"), None, None) => {}
+            (Some("$"), _, _, _) => return Err(src.error(line, "Expected '$ cd <dir>' or '$ ls'")),
+            (Some("dir"), Some(_), None, None) => {}
+            (Some(size), Some(_), None, None) => {
+                let size: usize = src.number(size)?;
+                if paths.is_empty() {
+                    return Err(src.error(line, "File listed outside of any directory"));
+                }
+                (0..paths.len())
+                    .map(|i| paths[0..=i].iter().map(|p| p.to_string()).collect::<Vec<_>>())
+                    .for_each(|subpath| {
+                        *sizes.entry(subpath).or_insert(0) += size;
+                    });
+            }
+            _ => return Err(src.error(line, "Expected a command, 'dir <name>' or '<size> <name>'")),
+        };
+    }
+    Ok(sizes)
+}
+
+pub fn calc_part1(sizes: &HashMap<Vec<String>, usize>, lim: usize) -> usize {
+    sizes.values().filter(|&&v| v <= lim).sum()
+}
+
+// None without a root directory, when no directory frees enough space or the space
+// needed does not fit in a usize.
+pub fn calc_part2(sizes: &HashMap<Vec<String>, usize>, tot: usize, free: usize) -> Option<usize> {
+    let used = sizes.get(&vec![String::from("/")])?;
+    let required = used.checked_add(free)?.saturating_sub(tot);
+    sizes.values().filter(|&&v| v >= required).min().copied()
+}
+
+pub struct Day7 {
+    pub limit: usize,
+    pub disk_size: usize,
+    pub needed: usize,
+}
+
+impl Default for Day7 {
+    fn default() -> Self {
+        Self { limit: 100000, disk_size: 70000000, needed: 30000000 }
+    }
+}
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    type Input = HashMap<Vec<String>, usize>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        calc_part1(input, self.limit).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        calc_part2(input, self.disk_size, self.needed).map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn options(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("limit", self.limit.to_string()),
+            ("disk-size", self.disk_size.to_string()),
+            ("needed", self.needed.to_string()),
+        ]
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "limit" => self.limit = option_value(name, value)?,
+            "disk-size" => self.disk_size = option_value(name, value)?,
+            "needed" => self.needed = option_value(name, value)?,
+            _ => return Err(unknown_option(name, &self.options())),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+        $ cd /
+        $ ls
+        dir a
+        14848514 b.txt
+        8504156 c.dat
+        dir d
+        $ cd a
+        $ ls
+        dir e
+        29116 f
+        2557 g
+        62596 h.lst
+        $ cd e
+        $ ls
+        584 i
+        $ cd ..
+        $ cd ..
+        $ cd d
+        $ ls
+        4060174 j
+        8033020 d.log
+        5626152 d.ext
+        7214296 k
+        "#
+    };
+
+    #[test]
+    fn test_part1() {
+        let sizes = parse_indata(TEST_DATA).unwrap();
+        assert_eq!(95437, calc_part1(&sizes, 100000));
+    }
+
+    #[test]
+    fn test_part2() {
+        let sizes = parse_indata(TEST_DATA).unwrap();
+        assert_eq!(Some(24933642), calc_part2(&sizes, 70000000, 30000000));
+        // Enough free space already, the smallest directory will do.
+        assert_eq!(Some(584), calc_part2(&sizes, 100000000, 30000000));
+        assert_eq!(Some(584), calc_part2(&sizes, 70000000, 1));
+        assert_eq!(None, calc_part2(&sizes, 70000000, 80000000));
+        assert_eq!(None, calc_part2(&parse_indata("$ cd x\n1 a\n").unwrap(), 70000000, 30000000));
+    }
+
+    #[test]
+    fn test_options() {
+        let mut day = Day7::default();
+        let sizes = day.parse(TEST_DATA).unwrap();
+        day.set_option("disk-size", "100000000").unwrap();
+        assert_eq!(Answer::Int(584), day.part2(&sizes));
+        day.set_option("disk-size", "70000000").unwrap();
+        day.set_option("needed", "1").unwrap();
+        assert_eq!(Answer::Int(584), day.part2(&sizes));
+        day.set_option("needed", "80000000").unwrap();
+        assert_eq!(Answer::Unsolved, day.part2(&sizes));
+        day.set_option("needed", &usize::MAX.to_string()).unwrap();
+        assert_eq!(Answer::Unsolved, day.part2(&day.parse("$ cd /\n1 a\n").unwrap()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("$ cd /\n$ cd ..\n$ cd ..\n").unwrap_err();
+        assert_eq!((3, 1), (err.line, err.column));
+        assert_eq!("Can not leave the root directory", err.message);
+
+        let err = parse_indata("$ cd /\n$ ls\n12x a.txt\n").unwrap_err();
+        assert_eq!((3, 1), (err.line, err.column));
+        assert_eq!("Invalid number '12x'", err.message);
+    }
+}
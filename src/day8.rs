@@ -0,0 +1,128 @@
+use crate::{
+    error::{ParseError, Source},
+    grid::{self, DIRS4},
+    image::{darken, Image, Palette},
+    solution::{Answer, Solution},
+};
+
+pub type Cell = (i32, bool);
+pub type Grid = grid::Grid<Cell>;
+
+pub fn parse_indata(input: &str) -> Result<Grid, ParseError> {
+    let src = Source::new(Day8::DAY, input);
+    Grid::parse(&src, |c| c.to_digit(10).map(|h| (h as i32, false)), "a tree height 0-9")
+}
+
+pub fn set_ray_visibility(grid: &mut Grid, start: (usize, usize), dir: (isize, isize)) {
+    let ray: Vec<(usize, usize)> = std::iter::once(start).chain(grid.ray(start, dir)).collect();
+    let mut threshold: i32 = -1;
+    for p in ray {
+        if grid[p].0 > threshold {
+            grid[p].1 = true;
+            threshold = grid[p].0;
+        }
+    }
+}
+
+pub fn set_visibility(grid: &mut Grid) {
+    let (rows, cols) = (grid.rows(), grid.cols());
+    for row in 0..rows {
+        set_ray_visibility(grid, (row, 0), (0, 1));
+        set_ray_visibility(grid, (row, cols - 1), (0, -1));
+    }
+
+    for col in 0..cols {
+        set_ray_visibility(grid, (0, col), (1, 0));
+        set_ray_visibility(grid, (rows - 1, col), (-1, 0));
+    }
+}
+
+pub fn count_trees(grid: &Grid, row: usize, col: usize, dr: isize, dc: isize) -> usize {
+    let height = grid[(row, col)].0;
+    let mut count = 0;
+    for p in grid.ray((row, col), (dr, dc)) {
+        count += 1;
+        if grid[p].0 >= height {
+            break;
+        }
+    }
+    count
+}
+
+pub fn calc_score(grid: &Grid, row: usize, col: usize) -> usize {
+    DIRS4.iter().map(|&(dr, dc)| count_trees(grid, row, col, dr, dc)).product()
+}
+
+pub fn get_max_score(grid: &Grid) -> usize {
+    grid.positions().map(|(row, col)| calc_score(grid, row, col)).max().unwrap_or(0)
+}
+
+pub fn count_visible(grid: & Grid) -> usize {
+    grid.iter().filter(|c| c.1).count()
+}
+
+// The trees coloured by height, the ones hidden from outside the grid darker.
+pub fn picture(grid: &Grid) -> Image {
+    let mut grid = grid.clone();
+    set_visibility(&mut grid);
+    grid.map(|(height, visible)| {
+        let colour = Palette::TERRAIN.value(*height as i64, 0, 9);
+        if *visible { colour } else { darken(colour, 0.5) }
+    })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Input = Grid;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let mut grid = input.clone();
+        set_visibility(&mut grid);
+        count_visible(&grid).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        get_max_score(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+    30373
+    25512
+    65332
+    33549
+    35390
+    "#
+    };
+
+    #[test]
+    fn test_part1() {
+        let mut grid = parse_indata(TEST_DATA).unwrap();
+        set_visibility(&mut grid);
+        assert_eq!(21, count_visible(&grid));
+    }
+
+    #[test]
+    fn test_part2() {
+        let grid = parse_indata(TEST_DATA).unwrap();
+        assert_eq!(8, get_max_score(&grid));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("303\n2x5\n").unwrap_err();
+        assert_eq!((8, 2, 2), (err.day, err.line, err.column));
+        assert_eq!("2x5", err.text);
+    }
+}
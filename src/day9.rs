@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+use regex::Regex;
+
+use crate::{
+    error::{ParseError, Source},
+    point::{Dir, Point2},
+    solution::{Answer, Solution},
+    visualize::{Canvas, Scene},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct Command {
+    pub dir: Dir,
+    pub steps: i32,
+}
+
+pub fn parse_indata(indata: &str) -> Result<Vec<Command>, ParseError> {
+    let src = Source::new(Day9::DAY, indata);
+    let re = Regex::new(r"^([UDLR]) (\d+)$").unwrap();
+    src.lines()
+        .map(|l| {
+            let cap = src.captures(&re, l, "<U|D|L|R> <steps>")?;
+            let dir = match &cap[1] {
+                "U" => Dir::Up,
+                "D" => Dir::Down,
+                "L" => Dir::Left,
+                _ => Dir::Right,
+            };
+            Ok(Command { dir, steps: src.number(&cap[2])? })
+        })
+        .collect()
+}
+
+pub type Coord = Point2<i32>;
+
+pub fn next_coord(head: &Coord, tail: &Coord) -> Coord {
+    let delta = *head - *tail;
+    if delta.chebyshev(Coord::zero()) <= 1 {
+        *tail
+    } else {
+        *tail + delta.signum()
+    }
+}
+
+// The knots of the rope, head first, and where the tail has been.
+pub struct Rope {
+    pub knots: Vec<Coord>,
+    pub visited: HashSet<Coord>,
+}
+
+impl Rope {
+    pub fn new(sz: usize) -> Self {
+        Self { knots: vec![Coord::zero(); sz], visited: HashSet::new() }
+    }
+
+    pub fn step(&mut self, dir: Dir) {
+        self.knots[0] += dir.delta();
+        for ix in 1..self.knots.len() {
+            self.knots[ix] = next_coord(&self.knots[ix-1], &self.knots[ix]);
+        }
+        self.visited.insert(self.knots[self.knots.len()-1]);
+    }
+}
+
+pub fn play_commands(commands: &[Command], sz: usize) -> HashSet<Coord> {
+    let mut rope = Rope::new(sz);
+    for cmd in commands {
+        for _ in 0..cmd.steps {
+            rope.step(cmd.dir);
+        }
+    }
+    rope.visited
+}
+
+// The rope moving one step at a time, with the positions the tail has visited.
+pub struct RopeScene<'a> {
+    pub rope: Rope,
+    commands: &'a [Command],
+    done: (usize, i32),
+}
+
+impl<'a> RopeScene<'a> {
+    pub fn new(commands: &'a [Command], sz: usize) -> Self {
+        Self { rope: Rope::new(sz), commands, done: (0, 0) }
+    }
+}
+
+impl Scene for RopeScene<'_> {
+    fn step(&mut self) -> bool {
+        // Moves of no steps are skipped, as in play_commands.
+        while self.commands.get(self.done.0).is_some_and(|cmd| cmd.steps <= 0) {
+            self.done.0 += 1;
+        }
+        let (ix, steps) = &mut self.done;
+        let Some(cmd) = self.commands.get(*ix) else {
+            return false;
+        };
+        self.rope.step(cmd.dir);
+        *steps += 1;
+        if *steps == cmd.steps {
+            self.done = (*ix + 1, 0);
+        }
+        true
+    }
+
+    fn draw(&self, canvas: &mut Canvas) {
+        // The canvas has y down.
+        let at = |p: &Coord| Coord::new(p.x, -p.y);
+        for p in &self.rope.visited {
+            canvas.set(at(p), '#');
+        }
+        canvas.set(at(&Coord::zero()), 's');
+        for (ix, knot) in self.rope.knots.iter().enumerate().rev() {
+            let ch = match (ix, self.rope.knots.len()) {
+                (0, _) => 'H',
+                (_, 2) => 'T',
+                _ => char::from_digit(ix as u32, 36).unwrap_or('*'),
+            };
+            canvas.set(at(knot), ch);
+        }
+        canvas.focus(at(&self.rope.knots[0]));
+    }
+
+    fn status(&self) -> String {
+        format!("Moves {}/{}, tail visited {}", self.done.0, self.commands.len(), self.rope.visited.len())
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Input = Vec<Command>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        play_commands(input, 2).len().into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        play_commands(input, 10).len().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+
+    #[test]
+    fn test_part1() {
+        let test_data: &str = indoc! {r#"
+        R 4
+        U 4
+        L 3
+        D 1
+        R 4
+        D 1
+        L 5
+        R 2
+        "#
+        };
+            let commands = parse_indata(test_data).unwrap();
+        let res = play_commands(&commands, 2);
+        assert_eq!(13, res.len());
+    }
+
+    #[test]
+    fn test_part2() {
+        let test_data: &'static str = indoc! {r#"
+        R 5
+        U 8
+        L 8
+        D 3
+        R 17
+        D 10
+        L 25
+        U 20
+        "#
+        };
+        let commands = parse_indata(test_data).unwrap();
+        let res = play_commands(&commands, 10);
+        assert_eq!(36, res.len());
+
+        let mut scene = RopeScene::new(&commands, 10);
+        while scene.step() {}
+        assert_eq!(36, scene.rope.visited.len());
+    }
+
+    #[test]
+    fn test_scene() {
+        let commands = parse_indata("R 2\nU 0\nU 1\nL 0\n").unwrap();
+        let mut scene = RopeScene::new(&commands, 2);
+        let mut steps = 0;
+        while scene.step() {
+            steps += 1;
+            assert!(steps <= 3);
+        }
+        assert_eq!(3, steps);
+        assert_eq!(Coord::new(2, 1), scene.rope.knots[0]);
+        assert_eq!("Moves 4/4, tail visited 2", scene.status());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_indata("R 4\nU 4\nX 3\n").unwrap_err();
+        assert_eq!((9, 3, 1), (err.day, err.line, err.column));
+        assert_eq!("X 3", err.text);
+    }
+
+}
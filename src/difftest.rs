@@ -0,0 +1,236 @@
+use std::{
+    fmt,
+    mem::discriminant,
+    ops::Range,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::{
+    generate::{generate, sensor_report, Rng},
+    reference, registry,
+    solution::{Answer, DynSolution},
+};
+
+// The reference solver of day 15 checks every position, so it gets a small search area.
+const DAY15_AREA: i64 = 40;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Answer(Answer),
+    Error(String),
+    Panic(String),
+}
+
+// Only the same answers agree, a parse error or a panic on either side never does.
+fn agrees(expected: &Outcome, actual: &Outcome) -> bool {
+    matches!((expected, actual), (Outcome::Answer(a), Outcome::Answer(b)) if a == b)
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Answer(Answer::Bitmap(rows)) => write!(f, "\n{}", rows.join("\n")),
+            Outcome::Answer(answer) => write!(f, "{answer}"),
+            Outcome::Error(e) => write!(f, "parse error: {e}"),
+            Outcome::Panic(msg) => write!(f, "panic: {msg}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub day: u32,
+    pub part: u32,
+    pub seed: u64,
+    pub input: String,
+    pub expected: Outcome,
+    pub actual: Outcome,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Day {} part {} disagrees on seed {}", self.day, self.part, self.seed)?;
+        writeln!(f, "Expected: {}", self.expected)?;
+        writeln!(f, "Actual: {}", self.actual)?;
+        write!(f, "Input:\n{}", self.input)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Comparison {
+    // The number of parts left out because the solver does not solve them.
+    Agrees { unsolved: usize },
+    Mismatch(Mismatch),
+    // Why the day was not compared.
+    Skipped(String),
+}
+
+pub fn outcome(solver: &dyn DynSolution, indata: &str, part: u32) -> Outcome {
+    match catch_unwind(AssertUnwindSafe(|| solver.solve(indata, part))) {
+        Ok(Ok(answer)) => Outcome::Answer(answer),
+        Ok(Err(e)) => Outcome::Error(e.to_string()),
+        Err(payload) => Outcome::Panic(match payload.downcast_ref::<&str>() {
+            Some(msg) => msg.to_string(),
+            None => payload.downcast_ref::<String>().cloned().unwrap_or_default(),
+        }),
+    }
+}
+
+// The solver and the reference solver of a day, with the same options.
+pub fn solvers(day: u32) -> Option<(Box<dyn DynSolution>, Box<dyn DynSolution>)> {
+    let (mut solver, mut naive) = (registry::solver(day)?, reference::solver(day)?);
+    if day == 15 {
+        for s in [&mut solver, &mut naive] {
+            s.set_option("row", &(DAY15_AREA / 2).to_string()).unwrap();
+            s.set_option("max-coord", &DAY15_AREA.to_string()).unwrap();
+        }
+    }
+    Some((solver, naive))
+}
+
+pub fn generate_input(day: u32, seed: u64, size: usize) -> Option<String> {
+    match day {
+        15 => Some(sensor_report(&mut Rng::new(seed), size, DAY15_AREA)),
+        _ => generate(day, seed, size),
+    }
+}
+
+// Repeatedly drops blank line separated blocks and then single lines, in halving chunks,
+// for as long as `fails` still holds for what is left.
+pub fn minimize(indata: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut current = indata.to_string();
+    for sep in ["\n\n", "\n"] {
+        let mut parts: Vec<String> = current.split_inclusive(sep).map(String::from).collect();
+        let mut chunk = (parts.len() / 2).max(1);
+        loop {
+            let mut removed = false;
+            let mut start = 0;
+            while start < parts.len() {
+                let end = (start + chunk).min(parts.len());
+                let candidate = [&parts[..start], &parts[end..]].concat().concat();
+                if fails(&candidate) {
+                    parts.drain(start..end);
+                    removed = true;
+                } else {
+                    start += chunk;
+                }
+            }
+            if !removed {
+                if chunk == 1 {
+                    break;
+                }
+                chunk /= 2;
+            }
+        }
+        current = parts.concat();
+    }
+    current
+}
+
+// Stops at the first generated input, by seed and then part, that the two solvers give
+// different answers for. Parts the solver does not solve are left out.
+pub fn check(day: u32, seeds: Range<u64>, size: usize) -> Comparison {
+    let Some((solver, naive)) = solvers(day) else {
+        return Comparison::Skipped(String::from("no reference solver"));
+    };
+    let (mut compared, mut unsolved) = (0, 0);
+    for seed in seeds {
+        let Some(indata) = generate_input(day, seed, size) else {
+            return Comparison::Skipped(String::from("no input generator"));
+        };
+        for part in 1..=2 {
+            let (expected, actual) = (outcome(naive.as_ref(), &indata, part), outcome(solver.as_ref(), &indata, part));
+            compared += 1;
+            if actual == Outcome::Answer(Answer::Unsolved) {
+                unsolved += 1;
+                continue;
+            }
+            if agrees(&expected, &actual) {
+                continue;
+            }
+            // Shrink while the disagreement stays of the same kind, a panic stays a panic.
+            let input = minimize(&indata, |candidate| {
+                let e = outcome(naive.as_ref(), candidate, part);
+                let a = outcome(solver.as_ref(), candidate, part);
+                !agrees(&e, &a)
+                    && a != Outcome::Answer(Answer::Unsolved)
+                    && discriminant(&e) == discriminant(&expected)
+                    && discriminant(&a) == discriminant(&actual)
+            });
+            let expected = outcome(naive.as_ref(), &input, part);
+            let actual = outcome(solver.as_ref(), &input, part);
+            return Comparison::Mismatch(Mismatch { day, part, seed, input, expected, actual });
+        }
+    }
+    if compared > 0 && unsolved == compared {
+        return Comparison::Skipped(String::from("not solved"));
+    }
+    Comparison::Agrees { unsolved }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimize() {
+        let indata = "1\n2\n\n3\n4\n\n5\n13\n6\n";
+        assert_eq!("13\n", minimize(indata, |s| s.lines().any(|l| l == "13")));
+        assert_eq!("2\n5\n", minimize(indata, |s| s.lines().any(|l| l == "2") && s.lines().any(|l| l == "5")));
+        assert_eq!(indata, minimize(indata, |s| s == indata));
+    }
+
+    #[test]
+    fn test_outcome() {
+        struct Broken;
+
+        impl crate::solution::Solution for Broken {
+            const DAY: u32 = 0;
+            type Input = ();
+
+            fn parse(&self, _indata: &str) -> Result<(), crate::error::ParseError> {
+                Ok(())
+            }
+
+            fn part1(&self, _input: &()) -> Answer {
+                panic!("Out of cheese")
+            }
+
+            fn part2(&self, _input: &()) -> Answer {
+                Answer::Unsolved
+            }
+        }
+
+        let panic = outcome(&Broken, "", 1);
+        assert_eq!(Outcome::Panic(String::from("Out of cheese")), panic);
+        let one = Outcome::Answer(Answer::Int(1));
+        assert!(agrees(&one, &one));
+        assert!(!agrees(&one, &outcome(&Broken, "", 2)));
+        assert!(!agrees(&Outcome::Error(String::from("x")), &Outcome::Error(String::from("x"))));
+        assert!(!agrees(&panic, &panic));
+    }
+
+    #[test]
+    fn test_small_sensor_area() {
+        let (solver, naive) = solvers(15).unwrap();
+        let indata = generate_input(15, 1, 10).unwrap();
+        let answer = naive.solve(&indata, 2).unwrap();
+        assert!(matches!(answer, Answer::Int(_)));
+        assert_eq!(Ok(answer), solver.solve(&indata, 2));
+    }
+
+    #[test]
+    fn test_generated_inputs_agree() {
+        for day in 1..=15 {
+            match check(day, 0..2, 10) {
+                // Day 14 part 1 is not solved, part 2 still has to agree.
+                Comparison::Agrees { unsolved } => assert_eq!(if day == 14 { 2 } else { 0 }, unsolved, "Day {day}"),
+                Comparison::Mismatch(mismatch) => panic!("{mismatch}"),
+                Comparison::Skipped(reason) => panic!("Day {day} skipped: {reason}"),
+            }
+        }
+        // Day 16 is not solved yet.
+        assert_eq!(Comparison::Skipped(String::from("not solved")), check(16, 0..2, 10));
+        assert_eq!(Comparison::Skipped(String::from("no reference solver")), check(17, 0..2, 10));
+    }
+}
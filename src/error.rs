@@ -0,0 +1,111 @@
+use regex::{Captures, Regex};
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Day {}, line {}, column {}: {}: {:?}",
+            self.day, self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+// The input a parser works on. Every &str handed to `error` must be a slice of
+// `indata`, its position is used to find the line and column of the error.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    pub day: u32,
+    pub indata: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: u32, indata: &'a str) -> Self {
+        Self { day, indata }
+    }
+
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        let start = self.indata.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|o| *o <= self.indata.len())
+            .unwrap_or(self.indata.len());
+        let line_start = self.indata[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = self.indata[offset..].find('\n').map(|i| i + offset).unwrap_or(self.indata.len());
+        ParseError {
+            day: self.day,
+            line: self.indata[..offset].matches('\n').count() + 1,
+            column: self.indata[line_start..offset].chars().count() + 1,
+            text: String::from(self.indata[line_start..line_end].trim_end_matches('\r')),
+            message: message.into(),
+        }
+    }
+
+    pub fn number<T: FromStr>(&self, s: &str) -> Result<T, ParseError> {
+        s.parse().map_err(|_| self.error(s, format!("Invalid number '{s}'")))
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.indata.lines().filter(|l| !l.is_empty())
+    }
+
+    pub fn nom_error(&self, err: nom::Err<nom::error::Error<&str>>, expected: &str) -> ParseError {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => self.error(e.input, format!("Expected '{expected}'")),
+            nom::Err::Incomplete(_) => self.error(&self.indata[self.indata.len()..], "Unexpected end of input"),
+        }
+    }
+
+    pub fn captures(&self, re: &Regex, line: &'a str, expected: &str) -> Result<Captures<'a>, ParseError> {
+        re.captures(line)
+            .ok_or_else(|| self.error(line, format!("Expected '{expected}'")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let indata = "12\n3x4\n";
+        let src = Source::new(1, indata);
+        let err = src.error(&indata[4..], "Unexpected 'x'");
+        assert_eq!(2, err.line);
+        assert_eq!(2, err.column);
+        assert_eq!("3x4", err.text);
+        assert_eq!("Day 1, line 2, column 2: Unexpected 'x': \"3x4\"", err.to_string());
+    }
+
+    #[test]
+    fn test_number() {
+        let indata = "12 ab";
+        let src = Source::new(3, indata);
+        assert_eq!(Ok(12), src.number::<i32>(&indata[0..2]));
+        let err = src.number::<i32>(&indata[3..]).unwrap_err();
+        assert_eq!((1, 4), (err.line, err.column));
+        assert_eq!("Invalid number 'ab'", err.message);
+    }
+
+    #[test]
+    fn test_captures() {
+        let indata = "1-2\n3+4\n";
+        let src = Source::new(4, indata);
+        let re = Regex::new(r"^(\d+)-(\d+)$").unwrap();
+        let lines: Vec<&str> = src.lines().collect();
+        assert_eq!("2", &src.captures(&re, lines[0], "a-b").unwrap()[2]);
+        let err = src.captures(&re, lines[1], "a-b").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+    }
+}
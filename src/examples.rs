@@ -0,0 +1,255 @@
+use crate::{
+    answers::{to_answer, Verdict},
+    input::normalize,
+    solution::{Answer, DynSolution},
+};
+use regex::Regex;
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value;
+
+// An example from a puzzle description with the answers it gives, and the puzzle
+// options it needs, such as the smaller row of the day 15 example.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub options: Vec<(String, String)>,
+}
+
+impl Example {
+    pub fn answer(&self, part: u32) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+// The fixture of a day in `dir`, normally the examples directory of the data directory.
+pub fn path(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("day{day}.toml"))
+}
+
+fn unescape(html: &str) -> String {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    tags.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn parse_answer(text: &str) -> Answer {
+    text.parse::<i64>().map_or_else(|_| Answer::Text(String::from(text)), Answer::Int)
+}
+
+// Finds the examples in a saved puzzle page, with one <article> per part. The example of
+// a part is the first <pre><code> block of its article and the answer is the last
+// emphasized word in it, the emphasized question at the end has spaces in it. A second
+// part without an example block of its own uses the one of the first part.
+pub fn extract(html: &str) -> Result<Vec<Example>, String> {
+    let article = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let block = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let emphasis = Regex::new(r"(?s)<em>(.*?)</em>").unwrap();
+
+    let mut examples: Vec<Example> = vec![];
+    for (ix, part) in article.captures_iter(html).take(2).enumerate() {
+        let text = &part[1];
+        let answer = emphasis
+            .captures_iter(text)
+            .map(|c| unescape(&c[1]))
+            .filter(|w| !w.is_empty() && !w.contains(char::is_whitespace))
+            .last()
+            .map(|w| parse_answer(&w));
+        let input = block.captures(text).map(|c| normalize(&unescape(&c[1])).into_owned());
+        let example = match (ix, input) {
+            (_, Some(input)) => {
+                examples.push(Example { input, ..Example::default() });
+                examples.last_mut().unwrap()
+            }
+            (1, None) if !examples.is_empty() => examples.last_mut().unwrap(),
+            _ => return Err(format!("No example in part {}", ix + 1)),
+        };
+        match ix {
+            0 => example.part1 = answer,
+            _ => example.part2 = answer,
+        }
+    }
+    if examples.is_empty() {
+        return Err(String::from("No puzzle description found, expected <article> elements"));
+    }
+    Ok(examples)
+}
+
+fn toml_string(s: &str) -> String {
+    match s.ends_with('\n') && !s.contains("'''") {
+        true => format!("'''\n{s}'''"),
+        false => Value::String(String::from(s)).to_string(),
+    }
+}
+
+fn toml_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(n) => n.to_string(),
+        Answer::Bitmap(rows) => toml_string(&(rows.join("\n") + "\n")),
+        answer => toml_string(&answer.to_string()),
+    }
+}
+
+pub fn to_toml(examples: &[Example]) -> String {
+    let mut res = String::new();
+    for (ix, example) in examples.iter().enumerate() {
+        if ix > 0 {
+            res.push('\n');
+        }
+        res.push_str("[[example]]\n");
+        for (key, answer) in [("part1", &example.part1), ("part2", &example.part2)] {
+            if let Some(answer) = answer {
+                writeln!(res, "{key} = {}", toml_answer(answer)).unwrap();
+            }
+        }
+        writeln!(res, "input = {}", toml_string(&example.input)).unwrap();
+        if !example.options.is_empty() {
+            res.push_str("[example.options]\n");
+            for (name, value) in &example.options {
+                writeln!(res, "{} = {}", Value::String(name.clone()), Value::String(value.clone())).unwrap();
+            }
+        }
+    }
+    res
+}
+
+pub fn parse(text: &str) -> Result<Vec<Example>, String> {
+    let table: Value = toml::from_str(text).map_err(|e| e.to_string())?;
+    let Some(list) = table.get("example").and_then(Value::as_array) else {
+        return Err(String::from("Expected [[example]] tables"));
+    };
+    list.iter()
+        .enumerate()
+        .map(|(ix, value)| {
+            let field = |key: &str| match value.get(key) {
+                Some(v) => to_answer(v).map(Some).ok_or(format!("example {}: {key} must be an integer or a string", ix + 1)),
+                None => Ok(None),
+            };
+            let input = value
+                .get("input")
+                .and_then(Value::as_str)
+                .ok_or(format!("example {}: input must be a string", ix + 1))?;
+            let options = match value.get("options").map(|o| o.as_table()) {
+                Some(Some(table)) => table
+                    .iter()
+                    .map(|(name, v)| (name.clone(), v.as_str().map_or_else(|| v.to_string(), String::from)))
+                    .collect(),
+                Some(None) => return Err(format!("example {}: options must be a table", ix + 1)),
+                None => vec![],
+            };
+            Ok(Example { input: String::from(input), part1: field("part1")?, part2: field("part2")?, options })
+        })
+        .collect()
+}
+
+pub fn load(path: &Path) -> Result<Vec<Example>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+// Runs `solver` on an example, for the parts the example has an answer for. The options
+// of the example are set on the solver and stay set.
+pub fn check(solver: &mut dyn DynSolution, example: &Example) -> Result<Vec<(u32, Verdict)>, String> {
+    for (name, value) in &example.options {
+        solver.set_option(name, value)?;
+    }
+    let input = solver.parse_any(&example.input).map_err(|e| e.to_string())?;
+    let mut verdicts = vec![];
+    for part in 1..=2 {
+        if let Some(expected) = example.answer(part) {
+            let actual = solver.part_any(input.as_ref(), part);
+            verdicts.push((part, match actual {
+                Answer::Unsolved => Verdict::Unknown(actual),
+                actual if actual == *expected => Verdict::Correct,
+                actual => Verdict::Wrong { expected: expected.clone(), actual },
+            }));
+        }
+    }
+    Ok(verdicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use indoc::indoc;
+
+    const PAGE: &str = indoc! {r#"
+        <main>
+        <article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+        <p>For example, suppose the Elves finish writing their items' <em>Calories</em>:</p>
+        <pre><code>1000
+        2000
+        3000
+
+        4000
+        </code></pre>
+        <p>In the example above, this is <em><code>6000</code></em> (carried by the first Elf).</p>
+        <p>Find the Elf carrying the most Calories. <em>How many total Calories is that Elf carrying?</em></p>
+        </article>
+        <p>Your puzzle answer was <code>66616</code>.</p>
+        <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+        <p>In the example above, the sum is <code><em>10000</em></code>.</p>
+        <p>Find the top three Elves. <em>How many Calories are they carrying in total?</em></p>
+        </article>
+        </main>
+    "#};
+
+    #[test]
+    fn test_extract() {
+        let examples = extract(PAGE).unwrap();
+        assert_eq!(1, examples.len());
+        assert_eq!("1000\n2000\n3000\n\n4000\n", examples[0].input);
+        assert_eq!(Some(Answer::Int(6000)), examples[0].part1);
+        assert_eq!(Some(Answer::Int(10000)), examples[0].part2);
+
+        let page = "<article><pre><code>a &lt;b&gt;\n  <em>c</em>\n</code></pre><p>Is <code><em>a-b</em></code></p></article>";
+        let examples = extract(page).unwrap();
+        assert_eq!("a <b>\n  c\n", examples[0].input);
+        assert_eq!(Some(Answer::Text(String::from("a-b"))), examples[0].part1);
+        assert!(extract("<p>Nothing here</p>").is_err());
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let mut examples = extract(PAGE).unwrap();
+        examples.push(Example {
+            input: String::from("x'''y"),
+            part1: Some(Answer::Text(String::from("ab\"c"))),
+            part2: None,
+            options: vec![(String::from("max-coord"), String::from("20"))],
+        });
+        assert_eq!(examples, parse(&to_toml(&examples)).unwrap());
+        assert!(parse("[[example]]\npart1 = 1\n").is_err());
+    }
+
+    // Runs every fixture in data/examples against its day.
+    #[test]
+    fn test_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/examples");
+        for mut solver in registry::solvers() {
+            let path = path(&dir, solver.day());
+            if !path.exists() {
+                continue;
+            }
+            for example in load(&path).unwrap() {
+                for (part, verdict) in check(solver.as_mut(), &example).unwrap() {
+                    assert!(!matches!(verdict, Verdict::Wrong { .. }), "day {} part {part}: {verdict}", solver.day());
+                }
+            }
+        }
+    }
+}
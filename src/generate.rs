@@ -0,0 +1,582 @@
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use itertools::Itertools;
+
+use crate::day13::{check_list_order, Item};
+
+pub const DEFAULT_SIZE: usize = 100;
+
+// SplitMix64. Kept in the crate so that a seed gives the same input on every platform
+// and with every version of the dependencies.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in 0..n, n must not be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, r: RangeInclusive<i64>) -> i64 {
+        r.start() + (self.next_u64() % (r.end() - r.start() + 1) as u64) as i64
+    }
+
+    // True with probability 1/n.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    pub fn letter(&mut self, letters: &[u8]) -> char {
+        *self.pick(letters) as char
+    }
+}
+
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// A random puzzle input for `day`, or None if there is no generator for it. The inputs
+// are valid for the solvers with their default options, `size` is roughly the number of
+// records, see the generator of each day for what it counts.
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    let size = size.max(1);
+    Some(match day {
+        1 => calories(rng, size),
+        2 => strategy_guide(rng, size),
+        3 => rucksacks(rng, size),
+        4 => section_pairs(rng, size),
+        5 => crate_stacks(rng, size),
+        6 => datastream(rng, size),
+        7 => terminal(rng, size),
+        8 => trees(rng, size),
+        9 => rope_moves(rng, size),
+        10 => cpu_program(rng, size),
+        11 => monkeys(rng, size),
+        12 => heightmap(rng, size),
+        13 => packets(rng, size),
+        14 => rock_paths(rng, size),
+        15 => sensors(rng, size),
+        16 => valves(rng, size),
+        _ => return None,
+    })
+}
+
+// `size` elves, at least three.
+fn calories(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(3))
+        .map(|_| (0..rng.range(1..=15)).map(|_| format!("{}\n", rng.range(1000..=60000))).collect::<String>())
+        .join("\n")
+}
+
+// `size` rounds.
+fn strategy_guide(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| format!("{} {}\n", rng.letter(b"ABC"), rng.letter(b"XYZ"))).collect()
+}
+
+// `size` rucksacks rounded up to whole groups of three. The halves of a rucksack share
+// exactly one item and the rucksacks of a group exactly one badge.
+fn rucksacks(rng: &mut Rng, size: usize) -> String {
+    let mut res = String::new();
+    for _ in 0..size.div_ceil(3) {
+        let mut items: Vec<u8> = LOWER.iter().chain(UPPER).copied().collect();
+        rng.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+        for own in items.chunks(items.len() / 3).take(3) {
+            let (shared, rest) = own.split_first().unwrap();
+            let (left, right) = rest.split_at(rest.len() / 2);
+            let half = rng.range(2..=16) as usize;
+            let mut l: Vec<u8> = vec![*shared, badge];
+            let mut r: Vec<u8> = vec![*shared];
+            l.extend((2..half).map(|_| *rng.pick(left)));
+            r.extend((1..half).map(|_| *rng.pick(right)));
+            rng.shuffle(&mut l);
+            rng.shuffle(&mut r);
+            res.push_str(&format!("{}{}\n", String::from_utf8(l).unwrap(), String::from_utf8(r).unwrap()));
+        }
+    }
+    res
+}
+
+// `size` pairs of section ranges.
+fn section_pairs(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let start = rng.range(1..=99);
+        (start, rng.range(start..=99))
+    };
+    (0..size)
+        .map(|_| {
+            let (a, b) = range(rng);
+            let (c, d) = range(rng);
+            format!("{a}-{b},{c}-{d}\n")
+        })
+        .collect()
+}
+
+// Three to nine stacks and `size` moves, no stack is ever emptied.
+fn crate_stacks(rng: &mut Rng, size: usize) -> String {
+    let n = rng.range(3..=9) as usize;
+    let mut stacks: Vec<Vec<char>> = (0..n).map(|_| (0..rng.range(1..=8)).map(|_| rng.letter(UPPER)).collect()).collect();
+    // Some stack needs a crate to spare for there to be any moves.
+    if stacks.iter().all(|s| s.len() == 1) {
+        stacks[0].push(rng.letter(UPPER));
+    }
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    let mut res = String::new();
+    for level in (0..height).rev() {
+        let row = stacks.iter().map(|s| s.get(level).map_or(String::from("   "), |c| format!("[{c}]"))).join(" ");
+        res.push_str(&row);
+        res.push('\n');
+    }
+    res.push_str(&(1..=n).map(|i| format!(" {i} ")).join(" "));
+    res.push_str("\n\n");
+    for _ in 0..size {
+        let movable: Vec<usize> = (0..n).filter(|i| stacks[*i].len() > 1).collect();
+        let from = *rng.pick(&movable);
+        let to = (from + 1 + rng.below(n - 1)) % n;
+        let count = rng.range(1..=stacks[from].len() as i64 - 1) as usize;
+        let at = stacks[from].len() - count;
+        let moved: Vec<char> = stacks[from].drain(at..).collect();
+        stacks[to].extend(moved);
+        res.push_str(&format!("move {count} from {} to {}\n", from + 1, to + 1));
+    }
+    res
+}
+
+// `size` characters, at least 20, with a run of 14 different ones somewhere.
+fn datastream(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(20);
+    let mut letters = LOWER.to_vec();
+    rng.shuffle(&mut letters);
+    let alphabet = &letters[..rng.range(4..=13) as usize];
+    let mut res: Vec<u8> = (0..size - 14).map(|_| *rng.pick(alphabet)).collect();
+    let at = rng.below(res.len() + 1);
+    rng.shuffle(&mut letters);
+    res.splice(at..at, letters[..14].iter().copied());
+    format!("{}\n", String::from_utf8(res).unwrap())
+}
+
+fn name(rng: &mut Rng, taken: &mut HashSet<String>) -> String {
+    loop {
+        let mut name: String = (0..rng.range(1..=8)).map(|_| rng.letter(LOWER)).collect();
+        if rng.one_in(3) {
+            name = format!("{name}.{}", (0..3).map(|_| rng.letter(LOWER)).collect::<String>());
+        }
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    files: Vec<(String, usize)>,
+}
+
+// The directory and all its subdirectories, as the transcript of a walk through them.
+fn list_dir(dirs: &[Dir], ix: usize, rng: &mut Rng, res: &mut String) {
+    let dir = &dirs[ix];
+    res.push_str(&format!("$ cd {}\n$ ls\n", dir.name));
+    let mut entries: Vec<String> = dir.dirs.iter().map(|d| format!("dir {}\n", dirs[*d].name)).collect();
+    entries.extend(dir.files.iter().map(|(name, size)| format!("{size} {name}\n")));
+    rng.shuffle(&mut entries);
+    res.extend(entries);
+    for d in &dir.dirs {
+        list_dir(dirs, *d, rng, res);
+        res.push_str("$ cd ..\n");
+    }
+}
+
+// `size` files in about a quarter as many directories. The disk usage is between 41 and
+// 69 million so there is always something to delete with the default disk size.
+fn terminal(rng: &mut Rng, size: usize) -> String {
+    let mut dirs = vec![Dir { name: String::from("/"), ..Dir::default() }];
+    let mut taken: Vec<HashSet<String>> = vec![HashSet::new()];
+    for _ in 0..size / 4 {
+        let parent = rng.below(dirs.len());
+        let name = name(rng, &mut taken[parent]);
+        let ix = dirs.len();
+        dirs[parent].dirs.push(ix);
+        dirs.push(Dir { name, ..Dir::default() });
+        taken.push(HashSet::new());
+    }
+    let weights: Vec<usize> = (0..size).map(|_| 10usize.pow(rng.range(0..=5) as u32) * rng.range(1..=9) as usize).collect();
+    let total: usize = weights.iter().sum();
+    let target = rng.range(41_000_000 + size as i64..=69_000_000) as usize;
+    for w in weights {
+        let dir = rng.below(dirs.len());
+        let name = name(rng, &mut taken[dir]);
+        dirs[dir].files.push((name, (w * target / total).max(1)));
+    }
+
+    let mut res = String::new();
+    list_dir(&dirs, 0, rng, &mut res);
+    res
+}
+
+// A `size` by `size` forest.
+fn trees(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| (0..size).map(|_| rng.letter(b"0123456789")).collect::<String>() + "\n").collect()
+}
+
+// `size` moves.
+fn rope_moves(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| format!("{} {}\n", rng.letter(b"UDLR"), rng.range(1..=20))).collect()
+}
+
+// A program that runs for exactly `size` screen rows of 40 cycles, at least six.
+fn cpu_program(rng: &mut Rng, size: usize) -> String {
+    let cycles = 40 * size.max(6);
+    let mut res = String::new();
+    let (mut cycle, mut x) = (0, 1);
+    while cycle < cycles {
+        if cycle + 1 == cycles || rng.one_in(3) {
+            res.push_str("noop\n");
+            cycle += 1;
+        } else {
+            let target = rng.range(-1..=40);
+            let value = if target == x { 1 } else { target - x };
+            res.push_str(&format!("addx {value}\n"));
+            x += value;
+            cycle += 2;
+        }
+    }
+    res
+}
+
+const PRIMES: [i64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+struct Monkey {
+    items: Vec<i64>,
+    op: (char, Option<i64>),
+    div_by: i64,
+    targets: (usize, usize),
+}
+
+impl Monkey {
+    fn apply(&self, worry: i128) -> i128 {
+        let value = self.op.1.map_or(worry, |n| n as i128);
+        if self.op.0 == '*' { worry * value } else { worry + value }
+    }
+}
+
+// The 20 rounds of part 1 have no modulo to keep the worry levels down, so they have
+// to be played to make sure they fit in an i64.
+fn fits_part1(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<Vec<i128>> = monkeys.iter().map(|m| m.items.iter().map(|i| *i as i128).collect()).collect();
+    for _ in 0..20 {
+        for (ix, m) in monkeys.iter().enumerate() {
+            for worry in std::mem::take(&mut items[ix]) {
+                let worry = m.apply(worry);
+                if worry > i64::MAX as i128 {
+                    return false;
+                }
+                let worry = worry / 3;
+                let to = if worry % m.div_by as i128 == 0 { m.targets.0 } else { m.targets.1 };
+                items[to].push(worry);
+            }
+        }
+    }
+    true
+}
+
+// `size` monkeys, between two and eight.
+fn monkeys(rng: &mut Rng, size: usize) -> String {
+    let n = size.clamp(2, 8);
+    let monkeys = loop {
+        let mut primes = PRIMES;
+        rng.shuffle(&mut primes);
+        let monkeys: Vec<Monkey> = (0..n)
+            .map(|id| {
+                let op = match rng.below(8) {
+                    0 => ('*', None),
+                    1 => ('+', None),
+                    2..=4 => ('*', Some(rng.range(2..=19))),
+                    _ => ('+', Some(rng.range(1..=8))),
+                };
+                let iftrue = (id + 1 + rng.below(n - 1)) % n;
+                let others: Vec<usize> = (0..n).filter(|t| *t != id && *t != iftrue).collect();
+                let iffalse = if others.is_empty() { iftrue } else { *rng.pick(&others) };
+                Monkey {
+                    items: (0..rng.range(1..=6)).map(|_| rng.range(50..=99)).collect(),
+                    op,
+                    div_by: primes[id],
+                    targets: (iftrue, iffalse),
+                }
+            })
+            .collect();
+        if fits_part1(&monkeys) {
+            break monkeys;
+        }
+    };
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(id, m)| {
+            let value = m.op.1.map_or(String::from("old"), |n| n.to_string());
+            format!(
+                "Monkey {id}:\n  Starting items: {}\n  Operation: new = old {} {value}\n  Test: divisible by {}\n    \
+                 If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                m.items.iter().join(", "),
+                m.op.0,
+                m.div_by,
+                m.targets.0,
+                m.targets.1
+            )
+        })
+        .join("\n")
+}
+
+// `size` columns, at least 40, and a third as many rows. The height grows with the
+// distance from S and there is always a staircase path from S up to E.
+fn heightmap(rng: &mut Rng, size: usize) -> String {
+    let cols = size.max(40) as i64;
+    let rows = (cols / 3).max(5);
+    let start = (rng.range(0..=rows - 1), rng.range(0..=cols / 8));
+    let end = (rng.range(0..=rows - 1), cols - 1 - rng.range(0..=cols / 8));
+    let dist = |p: (i64, i64)| (p.0 - start.0).abs() + (p.1 - start.1).abs();
+    let base = |p: (i64, i64)| (dist(p) * 25 / dist(end)).min(25);
+
+    let mut path = HashSet::from([start]);
+    let mut p = start;
+    while p != end {
+        let dr = (end.0 - p.0).signum();
+        p = if p.1 == end.1 || (dr != 0 && rng.one_in(2)) { (p.0 + dr, p.1) } else { (p.0, p.1 + 1) };
+        path.insert(p);
+    }
+    let mut res = String::new();
+    for r in 0..rows {
+        for c in 0..cols {
+            let mut height = base((r, c));
+            if !path.contains(&(r, c)) && rng.one_in(3) {
+                height -= rng.range(0..=height);
+            }
+            res.push(match (r, c) {
+                p if p == start => 'S',
+                p if p == end => 'E',
+                _ => LOWER[height as usize] as char,
+            });
+        }
+        res.push('\n');
+    }
+    res
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> Item {
+    Item::List(
+        (0..rng.range(0..=5))
+            .map(|_| match depth < 3 && rng.one_in(3) {
+                true => packet(rng, depth + 1),
+                false => Item::Int(rng.range(0..=10) as i32),
+            })
+            .collect(),
+    )
+}
+
+fn packet_text(item: &Item) -> String {
+    match item {
+        Item::Int(n) => n.to_string(),
+        Item::List(items) => format!("[{}]", items.iter().map(packet_text).join(",")),
+    }
+}
+
+// `size` pairs of packets, never in an undecided order and never ordered the same as a
+// divider packet, such as [6] and [[6]].
+fn packets(rng: &mut Rng, size: usize) -> String {
+    let dividers = [2, 6].map(|n| Item::List(vec![Item::List(vec![Item::Int(n)])]));
+    let decided = |p: &Item| dividers.iter().all(|d| check_list_order(p, d).is_some());
+    (0..size)
+        .map(|_| loop {
+            let (a, b) = (packet(rng, 0), packet(rng, 0));
+            if check_list_order(&a, &b).is_some() && decided(&a) && decided(&b) {
+                break format!("{}\n{}\n", packet_text(&a), packet_text(&b));
+            }
+        })
+        .join("\n")
+}
+
+// `size` rock paths below the sand source.
+fn rock_paths(rng: &mut Rng, size: usize) -> String {
+    let depth = 10 + size as i64 / 2;
+    (0..size)
+        .map(|_| {
+            let mut p = (rng.range(440..=560), rng.range(2..=depth));
+            let mut points = vec![p];
+            let horizontal = rng.one_in(2);
+            for i in 0..rng.range(1..=4) {
+                let len = rng.range(1..=8) * if rng.one_in(2) { -1 } else { 1 };
+                p = match (i % 2 == 0) == horizontal {
+                    true => (p.0 + len, p.1),
+                    false => (p.0, (p.1 + len).max(2)),
+                };
+                if p != *points.last().unwrap() {
+                    points.push(p);
+                }
+            }
+            if points.len() == 1 {
+                points.push((p.0 + 1, p.1));
+            }
+            points.iter().map(|(x, y)| format!("{x},{y}")).join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+fn sensors(rng: &mut Rng, size: usize) -> String {
+    sensor_report(rng, size, 4_000_000)
+}
+
+// `size` sensors besides the four that cover the whole search area of 0..=max but for
+// one spot. Each beacon is on the edge of its sensor's range, that it is the closest one
+// to the sensor is not guaranteed.
+pub fn sensor_report(rng: &mut Rng, size: usize, max: i64) -> String {
+    let hidden = (rng.range(0..=max), rng.range(0..=max));
+    let dist = |a: (i64, i64), b: (i64, i64)| (a.0 - b.0).abs() + (a.1 - b.1).abs();
+    let a = [hidden.0, max - hidden.0, hidden.1, max - hidden.1].into_iter().max().unwrap() + rng.range(1..=1000);
+    let mut sensors: Vec<((i64, i64), i64)> = [(a, a), (-a, a), (a, -a), (-a, -a)]
+        .into_iter()
+        .map(|(dx, dy)| ((hidden.0 + dx, hidden.1 + dy), 2 * a - 1))
+        .collect();
+    sensors.extend((0..size).map(|_| loop {
+        let s = (rng.range(-max / 10..=max * 11 / 10), rng.range(-max / 10..=max * 11 / 10));
+        if dist(s, hidden) > 1 {
+            break (s, rng.range(1..=dist(s, hidden) - 1));
+        }
+    }));
+    rng.shuffle(&mut sensors);
+    sensors
+        .into_iter()
+        .map(|(s, r)| {
+            let dx = rng.range(-r..=r);
+            let dy = (r - dx.abs()) * if rng.one_in(2) { -1 } else { 1 };
+            let b = (s.0 + dx, s.1 + dy);
+            format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}\n", s.0, s.1, b.0, b.1)
+        })
+        .collect()
+}
+
+// `size` valves, at least two, connected into one network that includes AA.
+fn valves(rng: &mut Rng, size: usize) -> String {
+    let n = size.clamp(2, 26 * 26);
+    let mut names: Vec<String> = UPPER.iter().cartesian_product(UPPER).map(|(a, b)| format!("{}{}", *a as char, *b as char)).collect();
+    names.retain(|name| name != "AA");
+    rng.shuffle(&mut names);
+    names.insert(0, String::from("AA"));
+    names.truncate(n);
+
+    let mut tunnels: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..n {
+        connect(i, rng.below(i));
+    }
+    for _ in 0..n / 2 {
+        connect(rng.below(n), rng.below(n));
+    }
+    let mut lines: Vec<String> = (0..n)
+        .map(|i| {
+            let flow = if i == 0 || rng.one_in(2) { 0 } else { rng.range(1..=25) };
+            let to = tunnels[i].iter().map(|t| &names[*t]).join(", ");
+            match tunnels[i].len() {
+                1 => format!("Valve {} has flow rate={flow}; tunnel leads to valve {to}\n", names[i]),
+                _ => format!("Valve {} has flow rate={flow}; tunnels lead to valves {to}\n", names[i]),
+            }
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        day15, day5,
+        registry::{solver, solvers},
+        solution::Answer,
+    };
+
+    #[test]
+    fn test_deterministic() {
+        for day in 1..=16 {
+            assert_eq!(generate(day, 7, 30), generate(day, 7, 30), "day {day}");
+            assert_ne!(generate(day, 7, 30), generate(day, 8, 30), "day {day}");
+        }
+        assert_eq!(None, generate(26, 0, 10));
+    }
+
+    #[test]
+    fn test_solvable() {
+        // Days added since have no generator yet.
+        for s in solvers().iter().filter(|s| s.day() <= 16) {
+            for seed in 0..3 {
+                let indata = generate(s.day(), seed, 20).unwrap();
+                let input = s.parse_any(&indata).unwrap_or_else(|e| panic!("seed {seed}: {e}\n{indata}"));
+                // Day 15 searches four million rows, too slow for a debug build.
+                if s.day() == 15 {
+                    continue;
+                }
+                for part in 1..=2 {
+                    let answer = s.part_any(input.as_ref(), part);
+                    let unsolved = matches!((s.day(), part), (14, 1) | (16, _));
+                    assert_eq!(unsolved, answer == Answer::Unsolved, "day {} part {part} seed {seed}", s.day());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_crates() {
+        // Every stack of seed 7200 starts with a single crate.
+        let indata = generate(5, 7200, 1).unwrap();
+        let (stacks, commands) = day5::parse_indata(&indata).unwrap();
+        assert_eq!(1, commands.len());
+        for part in 1..=2 {
+            match solver(5).unwrap().solve(&indata, part) {
+                Ok(Answer::Text(tops)) => assert_eq!(stacks.len(), tops.len()),
+                other => panic!("{other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_sensors_leave_one_spot() {
+        let zone = day15::parse_indata(&generate(15, 3, 10).unwrap()).unwrap();
+        let mut uncovered = 0;
+        for y in (0..=4_000_000).step_by(100_000) {
+            let (ranges, _) = day15::calc_ranges_for_line(y, &zone);
+            assert!(*ranges[0].start() <= 0 && *ranges[ranges.len() - 1].end() >= 4_000_000);
+            for (a, b) in ranges.iter().tuple_windows() {
+                assert_eq!(a.end() + 2, *b.start());
+                uncovered += 1;
+            }
+        }
+        assert!(uncovered <= 1);
+    }
+}
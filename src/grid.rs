@@ -0,0 +1,176 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::{ParseError, Source};
+
+// Directions as (row, column) deltas, clockwise starting upwards.
+pub const DIRS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const DIRS8: [(isize, isize); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+// A rectangular grid stored row by row. Positions are (row, column), `get` and the
+// neighbour and ray iterators only ever yield positions inside the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Self { rows, cols, data: vec![value; rows * cols] }
+    }
+}
+
+impl<T> Grid<T> {
+    // Panics if the rows are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map(|r| r.len()).unwrap_or(0);
+        assert!(rows.iter().all(|r| r.len() == cols), "All rows must have {cols} columns");
+        Self { rows: rows.len(), cols, data: rows.into_iter().flatten().collect() }
+    }
+
+    // Parses a character map with one row per non-empty line, `cell` returns None for
+    // characters that are not allowed and the error then says what was `expected`.
+    pub fn parse(src: &Source, cell: impl Fn(char) -> Option<T>, expected: &str) -> Result<Self, ParseError> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for line in src.lines() {
+            let mut row = Vec::new();
+            for (ix, c) in line.char_indices() {
+                row.push(cell(c).ok_or_else(|| src.error(&line[ix..], format!("Expected {expected}")))?);
+            }
+            if !rows.is_empty() && row.len() != rows[0].len() {
+                return Err(src.error(line, format!("Row has {} columns, expected {}", row.len(), rows[0].len())));
+            }
+            rows.push(row);
+        }
+        if rows.is_empty() {
+            return Err(src.error(src.indata, "No grid in input"));
+        }
+        Ok(Self::from_rows(rows))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, row: isize, col: isize) -> bool {
+        row >= 0 && col >= 0 && (row as usize) < self.rows && (col as usize) < self.cols
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<&T> {
+        self.contains(row, col).then(|| &self.data[row as usize * self.cols + col as usize])
+    }
+
+    pub fn get_mut(&mut self, row: isize, col: isize) -> Option<&mut T> {
+        match self.contains(row, col) {
+            true => Some(&mut self.data[row as usize * self.cols + col as usize]),
+            false => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| (i / cols, i % cols))
+    }
+
+    pub fn find<'a>(&'a self, pred: impl Fn(&T) -> bool + 'a) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.positions().filter(move |p| pred(&self[*p]))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &T> {
+        (0..self.rows).map(move |row| &self[(row, col)])
+    }
+
+    fn step(&self, (row, col): (usize, usize), (dr, dc): (isize, isize)) -> Option<(usize, usize)> {
+        let (r, c) = (row as isize + dr, col as isize + dc);
+        self.contains(r, c).then_some((r as usize, c as usize))
+    }
+
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DIRS4.iter().filter_map(move |d| self.step(pos, *d))
+    }
+
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DIRS8.iter().filter_map(move |d| self.step(pos, *d))
+    }
+
+    // The positions from `pos` in direction `dir` up to the edge, `pos` itself excluded.
+    pub fn ray(&self, pos: (usize, usize), dir: (isize, isize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(self.step(pos, dir), move |p| self.step(*p, dir))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid { rows: self.rows, cols: self.cols, data: self.data.iter().map(f).collect() }
+    }
+
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> Vec<String> {
+        (0..self.rows).map(|row| self.row(row).iter().map(&cell).collect()).collect()
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(row < self.rows && col < self.cols, "({row}, {col}) is outside the grid");
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(row < self.rows && col < self.cols, "({row}, {col}) is outside the grid");
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(indata: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(&Source::new(0, indata), |c| c.to_digit(10), "a digit")
+    }
+
+    #[test]
+    fn test_parse_render() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((2, 3), (grid.rows(), grid.cols()));
+        assert_eq!(6, grid[(1, 2)]);
+        assert_eq!(Some(&4), grid.get(1, 0));
+        assert_eq!(None, grid.get(-1, 0));
+        assert_eq!(None, grid.get(0, 3));
+        assert_eq!(vec!["123", "456"], grid.render(|d| char::from_digit(*d, 10).unwrap()));
+
+        let err = digits("123\n4x6\n").unwrap_err();
+        assert_eq!((2, 2), (err.line, err.column));
+        let err = digits("123\n45\n").unwrap_err();
+        assert_eq!("Row has 2 columns, expected 3", err.message);
+        assert!(digits("\n").is_err());
+    }
+
+    #[test]
+    fn test_neighbours_and_rays() {
+        let grid = digits("123\n456\n789\n").unwrap();
+        let values = |ps: Vec<(usize, usize)>| ps.into_iter().map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(vec![2, 4], values(grid.neighbours4((0, 0)).collect()));
+        assert_eq!(vec![2, 3, 6, 9, 8, 7, 4, 1], values(grid.neighbours8((1, 1)).collect()));
+        assert_eq!(vec![6, 3], values(grid.ray((2, 2), (-1, 0)).collect()));
+        assert_eq!(vec![5, 1], values(grid.ray((2, 2), (-1, -1)).collect()));
+        assert_eq!(Vec::<u32>::new(), values(grid.ray((0, 0), (0, -1)).collect()));
+        assert_eq!(vec![&2, &5, &8], grid.col(1).collect::<Vec<_>>());
+        assert_eq!(vec![(0, 1), (1, 0)], grid.find(|d| d % 2 == 0 && *d < 5).collect::<Vec<_>>());
+    }
+}
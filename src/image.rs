@@ -0,0 +1,295 @@
+use crate::{
+    grid::Grid,
+    visualize::{Canvas, Scene},
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+pub type Rgb = [u8; 3];
+pub type Image = Grid<Rgb>;
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Pgm,
+    Png,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(Format::Ppm),
+            "pgm" => Ok(Format::Pgm),
+            "png" => Ok(Format::Png),
+            _ => Err(format!("Invalid image format: {s}, expected ppm, pgm or png")),
+        }
+    }
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Self, String> {
+        path.extension().and_then(|e| e.to_str()).unwrap_or_default().parse()
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Pgm => "pgm",
+            Format::Png => "png",
+        }
+    }
+}
+
+// Colours spread evenly from 0 to 1, with the ones in between blended.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette(pub &'static [Rgb]);
+
+impl Palette {
+    pub const GRAY: Palette = Palette(&[BLACK, WHITE]);
+    pub const HEAT: Palette = Palette(&[[0, 0, 64], [160, 0, 128], [240, 80, 0], [255, 220, 0], [255, 255, 224]]);
+    pub const TERRAIN: Palette = Palette(&[[16, 64, 160], [32, 160, 64], [200, 180, 80], [128, 96, 64], [240, 240, 240]]);
+
+    pub fn at(&self, t: f64) -> Rgb {
+        let colours = self.0;
+        let pos = t.clamp(0.0, 1.0) * (colours.len() - 1) as f64;
+        let (ix, frac) = ((pos as usize).min(colours.len() - 2), pos - (pos as usize).min(colours.len() - 2) as f64);
+        let (a, b) = (colours[ix], colours[ix + 1]);
+        [0, 1, 2].map(|c| (a[c] as f64 + (b[c] as f64 - a[c] as f64) * frac).round() as u8)
+    }
+
+    // The colour of `value` when `min` to `max` covers the whole palette.
+    pub fn value(&self, value: i64, min: i64, max: i64) -> Rgb {
+        match max > min {
+            true => self.at((value - min) as f64 / (max - min) as f64),
+            false => self.at(0.0),
+        }
+    }
+}
+
+pub fn luma([r, g, b]: Rgb) -> u8 {
+    ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+pub fn darken(colour: Rgb, factor: f64) -> Rgb {
+    colour.map(|c| (c as f64 * factor).round() as u8)
+}
+
+// Every pixel becomes a `factor` by `factor` square.
+pub fn scale(image: &Image, factor: usize) -> Image {
+    let factor = factor.max(1);
+    let rows = (0..image.rows() * factor)
+        .map(|row| (0..image.cols() * factor).map(|col| image[(row / factor, col / factor)]).collect())
+        .collect();
+    Grid::from_rows(rows)
+}
+
+// The colours used for the characters of visualize::Canvas frames: heights as letters,
+// walls and rock as #, sand as o, and markers like S, E and H in red.
+pub fn char_colour(ch: char) -> Rgb {
+    match ch {
+        ' ' => BLACK,
+        '.' => [64, 64, 80],
+        '#' => [180, 180, 180],
+        'o' => [230, 200, 80],
+        '*' => [255, 140, 0],
+        '=' => [120, 80, 40],
+        'a'..='z' => darken(Palette::TERRAIN.value(ch as i64, 'a' as i64, 'z' as i64), 0.6),
+        '0'..='9' => Palette::HEAT.value(ch as i64, '0' as i64, '9' as i64),
+        _ => [255, 40, 40],
+    }
+}
+
+pub fn from_chars(cells: &Grid<char>, colour: impl Fn(char) -> Rgb) -> Image {
+    cells.map(|ch| colour(*ch))
+}
+
+fn netpbm(image: &Image, format: Format) -> Vec<u8> {
+    let (magic, pixels): (&str, Vec<u8>) = match format {
+        Format::Pgm => ("P5", image.iter().map(|c| luma(*c)).collect()),
+        _ => ("P6", image.iter().flatten().copied().collect()),
+    };
+    let mut res = format!("{magic}\n{} {}\n255\n", image.cols(), image.rows()).into_bytes();
+    res.extend(pixels);
+    res
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), x| {
+        let a = (a + *x as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+// A zlib stream of stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut res = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = match data.is_empty() {
+        true => vec![&[]],
+        false => data.chunks(0xffff).collect(),
+    };
+    for (ix, block) in blocks.iter().enumerate() {
+        res.push((ix + 1 == blocks.len()) as u8);
+        let len = block.len() as u16;
+        res.extend(len.to_le_bytes());
+        res.extend((!len).to_le_bytes());
+        res.extend(*block);
+    }
+    res.extend(adler32(data).to_be_bytes());
+    res
+}
+
+fn png(image: &Image) -> Vec<u8> {
+    let mut raw = vec![];
+    for row in 0..image.rows() {
+        // Filter type 0, the row as it is.
+        raw.push(0);
+        raw.extend(image.row(row).iter().flatten());
+    }
+    let mut header = vec![];
+    header.extend((image.cols() as u32).to_be_bytes());
+    header.extend((image.rows() as u32).to_be_bytes());
+    // 8 bit RGB, default compression and filtering, no interlace.
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut res = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    for (kind, data) in [(b"IHDR", header), (b"IDAT", zlib_stored(&raw)), (b"IEND", vec![])] {
+        res.extend((data.len() as u32).to_be_bytes());
+        let start = res.len();
+        res.extend(kind);
+        res.extend(data);
+        let crc = crc32(&res[start..]);
+        res.extend(crc.to_be_bytes());
+    }
+    res
+}
+
+// PPM and PNG are in colour, PGM is gray.
+pub fn encode(image: &Image, format: Format) -> Vec<u8> {
+    match format {
+        Format::Png => png(image),
+        _ => netpbm(image, format),
+    }
+}
+
+// Writes `image` in the format given by the extension of `path`.
+pub fn save(image: &Image, path: &Path) -> Result<(), String> {
+    let bytes = encode(image, Format::from_path(path)?);
+    fs::write(path, bytes).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+// Numbered frames in a directory: frame_00000.png, frame_00001.png and so on.
+pub struct Frames {
+    pub dir: PathBuf,
+    pub format: Format,
+    pub count: usize,
+}
+
+impl Frames {
+    pub fn new(dir: &Path, format: Format) -> Result<Self, String> {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        Ok(Self { dir: dir.to_path_buf(), format, count: 0 })
+    }
+
+    pub fn write(&mut self, image: &Image) -> Result<PathBuf, String> {
+        let path = self.dir.join(format!("frame_{:05}.{}", self.count, self.format.extension()));
+        fs::write(&path, encode(image, self.format)).map_err(|e| format!("{}: {e}", path.display()))?;
+        self.count += 1;
+        Ok(path)
+    }
+}
+
+// Plays `scene` to its end, writing a frame of `width` by `height` cells every `steps`
+// steps with each cell a `factor` pixels wide square.
+pub fn record(scene: &mut dyn Scene, frames: &mut Frames, steps: usize, (width, height): (usize, usize), factor: usize) -> Result<(), String> {
+    loop {
+        let mut canvas = Canvas::default();
+        scene.draw(&mut canvas);
+        frames.write(&scale(&from_chars(&canvas.window(width, height), char_colour), factor))?;
+        for _ in 0..steps.max(1) {
+            if !scene.step() {
+                let mut canvas = Canvas::default();
+                scene.draw(&mut canvas);
+                frames.write(&scale(&from_chars(&canvas.window(width, height), char_colour), factor))?;
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_palette() {
+        assert_eq!(BLACK, Palette::GRAY.at(0.0));
+        assert_eq!([128, 128, 128], Palette::GRAY.at(0.5));
+        assert_eq!(WHITE, Palette::GRAY.at(1.5));
+        assert_eq!([255, 220, 0], Palette::HEAT.value(3, 0, 4));
+        assert_eq!(Palette::HEAT.at(0.0), Palette::HEAT.value(5, 5, 5));
+    }
+
+    #[test]
+    fn test_encode() {
+        let image = Grid::from_rows(vec![vec![[255, 0, 0], WHITE], vec![BLACK, [0, 0, 255]]]);
+        let ppm = encode(&image, Format::Ppm);
+        assert_eq!(b"P6\n2 2\n255\n\xff\x00\x00\xff\xff\xff", &ppm[..17]);
+        assert_eq!(11 + 12, ppm.len());
+        assert_eq!(b"P5\n2 2\n255\n\x4c\xff\x00\x1d", &encode(&image, Format::Pgm)[..]);
+        assert_eq!(4, scale(&image, 2).cols());
+        assert_eq!([255, 0, 0], scale(&image, 2)[(1, 1)]);
+
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+        assert_eq!(0x11e6_0398, adler32(b"Wikipedia"));
+        let png = encode(&image, Format::Png);
+        assert_eq!(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x02\x08\x02", &png[..26]);
+        assert_eq!(b"IEND\xae\x42\x60\x82", &png[png.len() - 8..]);
+        let stored = zlib_stored(&vec![7; 70000]);
+        assert_eq!(2 + 5 + 65535 + 5 + 4465 + 4, stored.len());
+    }
+
+    #[test]
+    fn test_frames() {
+        struct Count(i32);
+
+        impl Scene for Count {
+            fn step(&mut self) -> bool {
+                self.0 += 1;
+                self.0 < 5
+            }
+
+            fn draw(&self, canvas: &mut Canvas) {
+                canvas.set(crate::visualize::Pos::new(self.0, 0), '#');
+            }
+        }
+
+        let dir = env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut frames = Frames::new(&dir, Format::Pgm).unwrap();
+        record(&mut Count(0), &mut frames, 2, (3, 1), 2).unwrap();
+        assert_eq!(4, frames.count);
+        assert_eq!(b"P5\n6 2\n255\n", &fs::read(dir.join("frame_00003.pgm")).unwrap()[..11]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
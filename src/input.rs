@@ -0,0 +1,163 @@
+use crate::solution::{unknown_option, DynSolution};
+use std::{
+    borrow::Cow,
+    env,
+    ffi::OsString,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::PathBuf,
+    process,
+};
+
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+fn data_dir_from(var: Option<OsString>) -> PathBuf {
+    var.filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("data"))
+}
+
+pub fn data_dir() -> PathBuf {
+    data_dir_from(env::var_os(DATA_DIR_VAR))
+}
+
+pub fn default_path(day: u32) -> PathBuf {
+    data_dir().join(format!("day{day}.txt"))
+}
+
+// The input as the parsers expect it: "\n" line endings, no trailing whitespace on any
+// line and exactly one newline after the last line, so that a file saved on Windows or
+// without a final newline gives the same answers.
+pub fn normalize(indata: &str) -> Cow<'_, str> {
+    let mut res = String::with_capacity(indata.len() + 1);
+    for line in indata.lines() {
+        res.push_str(line.trim_end());
+        res.push('\n');
+    }
+    res.truncate(res.trim_end_matches('\n').len());
+    if !res.is_empty() {
+        res.push('\n');
+    }
+    match res == indata {
+        true => Cow::Borrowed(indata),
+        false => Cow::Owned(res),
+    }
+}
+
+// `input` is a path, "-" for stdin or None for the default file in the data directory.
+pub fn read_input(day: u32, input: Option<&str>) -> Result<String, String> {
+    read_raw_input(day, input).map(|indata| normalize(&indata).into_owned())
+}
+
+// As read_input, but for reading a line at a time. The lines are not normalized.
+pub fn open_input(day: u32, input: Option<&str>) -> Result<Box<dyn BufRead>, String> {
+    let path = match input {
+        Some("-") => return Ok(Box::new(io::stdin().lock())),
+        Some(path) => PathBuf::from(path),
+        None => default_path(day),
+    };
+    let file = File::open(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+fn read_raw_input(day: u32, input: Option<&str>) -> Result<String, String> {
+    match input {
+        Some("-") => {
+            let mut indata = String::new();
+            io::stdin()
+                .read_to_string(&mut indata)
+                .map_err(|e| format!("stdin: {e}"))?;
+            Ok(indata)
+        }
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("{path}: {e}")),
+        None => {
+            let path = default_path(day);
+            fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))
+        }
+    }
+}
+
+pub type Options<'a> = Vec<(&'a str, &'a str)>;
+
+// Splits the arguments into the --input value and the --<name> <value> puzzle options.
+pub fn parse_args(args: &[String]) -> Result<(Option<&str>, Options<'_>), String> {
+    let mut input = None;
+    let mut options = Vec::new();
+    for pair in args.chunks(2) {
+        match pair {
+            [flag, value] if flag == "--input" => input = Some(value.as_str()),
+            [flag, value] if flag.starts_with("--") => options.push((&flag[2..], value.as_str())),
+            _ => return Err(String::from("Usage: [--input <path|->] [--<option> <value> ...]")),
+        }
+    }
+    Ok((input, options))
+}
+
+pub fn set_options(solution: &mut dyn DynSolution, options: &[(&str, &str)]) -> Result<(), String> {
+    options.iter().try_for_each(|(name, value)| solution.set_option(name, value))
+}
+
+pub fn or_exit<T, E: Display>(res: Result<T, E>) -> T {
+    res.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1)
+    })
+}
+
+// Reads the input for a day binary as selected by its command line arguments.
+pub fn from_args(day: u32) -> String {
+    let args: Vec<String> = env::args().skip(1).collect();
+    or_exit(read_args(day, &args))
+}
+
+pub fn read_args(day: u32, args: &[String]) -> Result<String, String> {
+    parse_args(args).and_then(|(input, options)| match options.first() {
+        Some((name, _)) => Err(unknown_option(name, &[])),
+        None => read_input(day, input),
+    })
+}
+
+// As from_args, but also sets the puzzle options given on the command line.
+pub fn from_args_with(solution: &mut dyn DynSolution) -> String {
+    let args: Vec<String> = env::args().skip(1).collect();
+    or_exit(parse_args(&args).and_then(|(input, options)| {
+        set_options(solution, &options)?;
+        read_input(solution.day(), input)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_dir() {
+        assert_eq!(PathBuf::from("data"), data_dir_from(None));
+        assert_eq!(PathBuf::from("data"), data_dir_from(Some(OsString::new())));
+        assert_eq!(PathBuf::from("/tmp/aoc"), data_dir_from(Some(OsString::from("/tmp/aoc"))));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let expected = "1 2\n\n3\n";
+        for indata in ["1 2\n\n3\n", "1 2\r\n\r\n3\r\n", "1 2  \n \n3", "1 2\t\n\n3\n\n\n", "1 2\r\n\n3  \r\n\r\n"] {
+            assert_eq!(expected, normalize(indata), "{indata:?}");
+        }
+        assert!(matches!(normalize(expected), Cow::Borrowed(_)));
+        assert_eq!("", normalize("\n \r\n"));
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(Ok((None, vec![])), parse_args(&args(&[])));
+        assert_eq!(Ok((Some("-"), vec![])), parse_args(&args(&["--input", "-"])));
+        assert_eq!(
+            Ok((Some("x.txt"), vec![("row", "10")])),
+            parse_args(&args(&["--row", "10", "--input", "x.txt"]))
+        );
+        assert!(parse_args(&args(&["--input"])).is_err());
+        assert!(parse_args(&args(&["x.txt"])).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{
+    day2::{calc_choice, moves, process, Game, Interpretation, Outcome, Round},
+    output::{csv_row, json_object, json_rows, json_string, Format},
+};
+use itertools::Itertools;
+use std::fmt;
+
+// The most readings that are ranked, that many take a few seconds on the real guide.
+pub const READING_LIMIT: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    Moves,
+    Outcomes,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Kind::Moves => "moves",
+            Kind::Outcomes => "outcomes",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reading {
+    pub kind: Kind,
+    // The move or outcome each symbol stands for.
+    pub mapping: Vec<(char, String)>,
+    pub score: i32,
+    // The rounds the player loses, draws and wins.
+    pub outcomes: [usize; 3],
+    // The puzzle part that reads the guide this way, if any.
+    pub part: Option<u32>,
+}
+
+impl Reading {
+    fn mapping_text(&self) -> String {
+        self.mapping.iter().map(|(s, name)| format!("{s}={name}")).join(" ")
+    }
+}
+
+// The losses, draws and wins of the player, None when a round can not be read.
+fn tally(game: &Game, strategy: &[Round], f: Interpretation) -> Option<[usize; 3]> {
+    let mut res = [0; 3];
+    for round in strategy {
+        let (opponent, player) = f(game, round)?;
+        res[game.outcome(opponent, player) as usize] += 1;
+    }
+    Some(res)
+}
+
+fn reading(kind: Kind, game: &Game, strategy: &[Round], mapping: Vec<(char, String)>, part: Option<u32>) -> Option<Reading> {
+    let f = match kind {
+        Kind::Moves => moves,
+        Kind::Outcomes => calc_choice,
+    };
+    Some(Reading { kind, mapping, score: process(game, strategy, f)?, outcomes: tally(game, strategy, f)?, part })
+}
+
+// The symbols of the second column, in order.
+pub fn symbols(strategy: &[Round]) -> Vec<char> {
+    strategy.iter().map(|r| r.1).sorted().dedup().collect()
+}
+
+// The number of ways to give each of `symbols` a different one of `choices`.
+fn arrangements(choices: usize, symbols: usize) -> Option<usize> {
+    match choices.checked_sub(symbols) {
+        Some(rest) => (rest + 1..=choices).try_fold(1usize, |n, c| n.checked_mul(c)),
+        None => Some(0),
+    }
+}
+
+// All readings, best first. Symbols can only be outcomes when there are at most three.
+pub fn readings(game: &Game, strategy: &[Round]) -> Result<Vec<Reading>, String> {
+    let symbols = symbols(strategy);
+    let count = arrangements(game.moves.len(), symbols.len()).and_then(|n| n.checked_add(arrangements(Outcome::ALL.len(), symbols.len())?));
+    if count.is_none_or(|n| n > READING_LIMIT) {
+        return Err(format!(
+            "{} moves and {} symbols give more than {READING_LIMIT} readings to rank",
+            game.moves.len(),
+            symbols.len()
+        ));
+    }
+    let mut res = vec![];
+    for assigned in (0..game.moves.len()).permutations(symbols.len()) {
+        // A symbol nowhere in the guide for the moves without one.
+        let mut remapped = game.clone();
+        for m in remapped.moves.iter_mut() {
+            m.player = '\0';
+        }
+        for (symbol, ix) in symbols.iter().zip(&assigned) {
+            remapped.moves[*ix].player = *symbol;
+        }
+        let part = symbols.iter().zip(&assigned).all(|(s, ix)| game.moves[*ix].player == *s).then_some(1);
+        let mapping = symbols.iter().zip(&assigned).map(|(s, ix)| (*s, game.moves[*ix].name.clone())).collect();
+        res.extend(reading(Kind::Moves, &remapped, strategy, mapping, part));
+    }
+    for assigned in Outcome::ALL.iter().permutations(symbols.len()) {
+        let mut remapped = game.clone();
+        for (outcome, entry) in Outcome::ALL.iter().zip(remapped.outcomes.iter_mut()) {
+            entry.0 = symbols.iter().zip(&assigned).find(|(_, o)| **o == outcome).map_or('\0', |(s, _)| *s);
+        }
+        let part = symbols.iter().zip(&assigned).all(|(s, o)| game.outcome_of(*s) == Some(**o)).then_some(2);
+        let mapping = symbols.iter().zip(&assigned).map(|(s, o)| (*s, String::from(o.name()))).collect();
+        res.extend(reading(Kind::Outcomes, &remapped, strategy, mapping, part));
+    }
+    res.sort_by(|a, b| b.score.cmp(&a.score).then(a.kind.cmp(&b.kind)).then(a.mapping.cmp(&b.mapping)));
+    Ok(res)
+}
+
+// A ranked table as text, or the readings as json or csv rows. At most `top` readings.
+pub fn format_readings(format: Format, readings: &[Reading], top: usize) -> Vec<String> {
+    let readings = &readings[..top.min(readings.len())];
+    let part = |r: &Reading| r.part.map_or(String::new(), |p| format!(" (part {p})"));
+    match format {
+        Format::Text => {
+            let mut res = vec![format!("{:>4} {:>8} {:>6} {:>6} {:>6}  {:<8} {}", "Rank", "Score", "Wins", "Draws", "Losses", "As", "Mapping")];
+            for (ix, r) in readings.iter().enumerate() {
+                let [lose, draw, win] = r.outcomes;
+                res.push(format!("{:>4} {:>8} {win:>6} {draw:>6} {lose:>6}  {:<8} {}{}", ix + 1, r.score, r.kind, r.mapping_text(), part(r)));
+            }
+            res
+        }
+        Format::Json => json_rows(
+            readings
+                .iter()
+                .enumerate()
+                .map(|(ix, r)| {
+                    let [lose, draw, win] = r.outcomes;
+                    let symbols: Vec<String> = r.mapping.iter().map(|(s, _)| s.to_string()).collect();
+                    let mapping: Vec<(&str, String)> = symbols.iter().zip(&r.mapping).map(|(s, (_, name))| (s.as_str(), json_string(name))).collect();
+                    json_object(&[
+                        ("rank", (ix + 1).to_string()),
+                        ("as", json_string(&r.kind.to_string())),
+                        ("mapping", json_object(&mapping)),
+                        ("score", r.score.to_string()),
+                        ("wins", win.to_string()),
+                        ("draws", draw.to_string()),
+                        ("losses", lose.to_string()),
+                        ("part", r.part.map_or(String::from("null"), |p| p.to_string())),
+                    ])
+                })
+                .collect(),
+        ),
+        Format::Csv => {
+            let mut res = vec![String::from("rank,as,mapping,score,wins,draws,losses,part")];
+            for (ix, r) in readings.iter().enumerate() {
+                let [lose, draw, win] = r.outcomes;
+                let part = r.part.map_or(String::new(), |p| p.to_string());
+                res.push(csv_row(&[
+                    (ix + 1).to_string(),
+                    r.kind.to_string(),
+                    r.mapping_text(),
+                    r.score.to_string(),
+                    win.to_string(),
+                    draw.to_string(),
+                    lose.to_string(),
+                    part,
+                ]));
+            }
+            res
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day2::parse_indata;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn test_readings() {
+        let game = Game::default();
+        let strategy = parse_indata(&game, EXAMPLE).unwrap();
+        let readings = readings(&game, &strategy).unwrap();
+        assert_eq!(12, readings.len());
+
+        let part1 = readings.iter().find(|r| r.part == Some(1)).unwrap();
+        assert_eq!((Kind::Moves, 15, [1, 1, 1]), (part1.kind, part1.score, part1.outcomes));
+        let part2 = readings.iter().find(|r| r.part == Some(2)).unwrap();
+        assert_eq!((Kind::Outcomes, 12, [1, 1, 1]), (part2.kind, part2.score, part2.outcomes));
+
+        // Each symbol as the move beating the opponent of its only round wins them all.
+        assert_eq!(Kind::Moves, readings[0].kind);
+        assert_eq!("X=scissors Y=paper Z=rock", readings[0].mapping_text());
+        assert_eq!(([0, 0, 3], 2 + 3 + 1 + 3 * 6), (readings[0].outcomes, readings[0].score));
+        assert!(readings.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_more_symbols() {
+        // Four symbols can not all be outcomes.
+        let game = Game::named("rock-paper-scissors-lizard-spock").unwrap();
+        let strategy = parse_indata(&game, "A V\nB W\nC X\nD Y\n").unwrap();
+        let readings = readings(&game, &strategy).unwrap();
+        assert_eq!(5 * 4 * 3 * 2, readings.len());
+        assert!(readings.iter().all(|r| r.kind == Kind::Moves));
+        assert_eq!(1, readings.iter().filter(|r| r.part.is_some()).count());
+    }
+
+    #[test]
+    fn test_limit() {
+        assert_eq!(Some(15 * 14 * 13), arrangements(15, 3));
+        assert_eq!(Some(0), arrangements(3, 4));
+        assert_eq!(None, arrangements(usize::MAX, 3));
+
+        let mut game = Game::default();
+        for ix in 0..12 {
+            let mut extra = game.moves[0].clone();
+            extra.name = format!("extra{ix}");
+            game.moves.push(extra);
+        }
+        let strategy: Vec<Round> = "ABCDEFGHIJ".chars().map(|s| ('A', s)).collect();
+        assert_eq!(
+            Err(String::from("15 moves and 10 symbols give more than 10000 readings to rank")),
+            readings(&game, &strategy)
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let game = Game::default();
+        let readings = readings(&game, &parse_indata(&game, EXAMPLE).unwrap()).unwrap();
+        let text = format_readings(Format::Text, &readings, 100);
+        assert_eq!(13, text.len());
+        assert_eq!("Rank    Score   Wins  Draws Losses  As       Mapping", text[0]);
+        assert_eq!("   5       15      1      1      1  moves    X=rock Y=paper Z=scissors (part 1)", text[5]);
+        assert_eq!("  11       12      1      1      1  outcomes X=lose Y=draw Z=win (part 2)", text[11]);
+
+        let json = format_readings(Format::Json, &readings, 2);
+        assert_eq!(4, json.len());
+        assert_eq!(
+            "  {\"rank\": 2, \"as\": \"outcomes\", \"mapping\": {\"X\": \"win\", \"Y\": \"lose\", \"Z\": \"draw\"}, \"score\": 18, \"wins\": 1, \"draws\": 1, \"losses\": 1, \"part\": null}",
+            json[2]
+        );
+        let csv = format_readings(Format::Csv, &readings, 1);
+        assert_eq!(vec!["rank,as,mapping,score,wins,draws,losses,part", "1,moves,X=scissors Y=paper Z=rock,24,3,0,0,"], csv);
+    }
+
+    #[test]
+    fn test_format_escapes() {
+        let mut game = Game::default();
+        game.moves[2].name = String::from("big \"scissors\", sharp");
+        let readings = readings(&game, &parse_indata(&game, EXAMPLE).unwrap()).unwrap();
+        let json = format_readings(Format::Json, &readings, 1);
+        assert!(json[1].contains("\"X\": \"big \\\"scissors\\\", sharp\""), "{}", json[1]);
+        let csv = format_readings(Format::Csv, &readings, 1);
+        assert_eq!("1,moves,\"X=big \"\"scissors\"\", sharp Y=paper Z=rock\",24,3,0,0,", csv[1]);
+    }
+}
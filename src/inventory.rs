@@ -0,0 +1,272 @@
+use crate::{
+    day1::Day1,
+    error::{ParseError, Source},
+    output::{csv_row, json_array, json_fields, json_object, Format},
+    solution::Solution,
+};
+
+pub const PERCENTILES: [u32; 6] = [10, 25, 50, 75, 90, 99];
+
+// Histogram bars are at most this wide.
+const BAR_WIDTH: usize = 50;
+
+// An elf with the number of items it carries, elves are numbered from 1 in input order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElfStats {
+    pub number: usize,
+    pub items: usize,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spread {
+    pub min: i64,
+    pub max: i64,
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl Spread {
+    // None without values.
+    fn new(sorted: &[i64]) -> Option<Self> {
+        Some(Self {
+            min: *sorted.first()?,
+            max: *sorted.last()?,
+            mean: sorted.iter().map(|v| *v as f64).sum::<f64>() / sorted.len() as f64,
+            median: percentile(sorted, 50.0),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bin {
+    pub from: i64,
+    pub to: i64,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub elves: Vec<ElfStats>,
+    pub totals: Spread,
+    pub items: Spread,
+    pub percentiles: Vec<(u32, f64)>,
+    pub histogram: Vec<Bin>,
+    // Totals outside these are outliers.
+    pub fences: (f64, f64),
+    pub outliers: Vec<ElfStats>,
+}
+
+// The value at `p` percent of the sorted values, interpolating between the two nearest.
+pub fn percentile(sorted: &[i64], p: f64) -> f64 {
+    let Some(last) = sorted.len().checked_sub(1) else {
+        return f64::NAN;
+    };
+    let rank = p.clamp(0.0, 100.0) / 100.0 * last as f64;
+    let (below, frac) = (rank.floor() as usize, rank.fract());
+    let above = (below + 1).min(last);
+    sorted[below] as f64 + (sorted[above] - sorted[below]) as f64 * frac
+}
+
+// `bins` bins of equal width from the smallest to the largest value, the last one
+// includes the largest value.
+pub fn histogram(sorted: &[i64], bins: usize) -> Vec<Bin> {
+    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+        return vec![];
+    };
+    let bins = bins.max(1) as i64;
+    let width = ((max - min) / bins + 1).max(1);
+    let mut res: Vec<Bin> = (0..bins).map(|ix| Bin { from: min + ix * width, to: min + (ix + 1) * width - 1, count: 0 }).collect();
+    for v in sorted {
+        res[((v - min) / width) as usize].count += 1;
+    }
+    while res.len() > 1 && res.last().is_some_and(|b| b.count == 0) {
+        res.pop();
+    }
+    res
+}
+
+// Numbered as day1::top_elves does, blocks without items are not elves. Totals that do
+// not fit are an error, as they are for top_elves.
+pub fn elves(indata: &str) -> Result<Vec<ElfStats>, ParseError> {
+    let src = Source::new(Day1::DAY, indata);
+    let mut res = vec![];
+    for block in indata.split("\n\n") {
+        let items: Vec<&str> = block.split('\n').filter(|v| !v.is_empty()).collect();
+        if items.is_empty() {
+            continue;
+        }
+        let total = items.iter().try_fold(0i64, |sum, item| {
+            sum.checked_add(src.number(item)?).ok_or_else(|| src.error(item, "Total calories too large"))
+        })?;
+        res.push(ElfStats { number: res.len() + 1, items: items.len(), total });
+    }
+    Ok(res)
+}
+
+// None without elves. Outliers are outside Tukey's fences, 1.5 times the interquartile
+// range beyond the first and third quartiles.
+pub fn report(elves: Vec<ElfStats>, bins: usize) -> Option<Report> {
+    let mut totals: Vec<i64> = elves.iter().map(|e| e.total).collect();
+    totals.sort_unstable();
+    let mut items: Vec<i64> = elves.iter().map(|e| e.items as i64).collect();
+    items.sort_unstable();
+
+    let (q1, q3) = (percentile(&totals, 25.0), percentile(&totals, 75.0));
+    let fences = (q1 - 1.5 * (q3 - q1), q3 + 1.5 * (q3 - q1));
+    let outliers = elves.iter().filter(|e| (e.total as f64) < fences.0 || (e.total as f64) > fences.1).copied().collect();
+    Some(Report {
+        totals: Spread::new(&totals)?,
+        items: Spread::new(&items)?,
+        percentiles: PERCENTILES.iter().map(|p| (*p, percentile(&totals, *p as f64))).collect(),
+        histogram: histogram(&totals, bins),
+        fences,
+        outliers,
+        elves,
+    })
+}
+
+pub fn from_input(indata: &str, bins: usize) -> Result<Option<Report>, ParseError> {
+    Ok(report(elves(indata)?, bins))
+}
+
+fn is_outlier(report: &Report, elf: &ElfStats) -> bool {
+    report.outliers.iter().any(|o| o.number == elf.number)
+}
+
+fn text(report: &Report) -> Vec<String> {
+    let mut res = vec![format!("{:<14} {}", "Elves", report.elves.len())];
+    let (t, i) = (&report.totals, &report.items);
+    for (name, total, items) in [
+        ("Min", t.min.to_string(), i.min.to_string()),
+        ("Mean", format!("{:.1}", t.mean), format!("{:.1}", i.mean)),
+        ("Median", format!("{:.1}", t.median), format!("{:.1}", i.median)),
+        ("Max", t.max.to_string(), i.max.to_string()),
+    ] {
+        res.push(format!("{name:<14} {total:>12} calories {items:>6} items"));
+    }
+    for (p, value) in &report.percentiles {
+        res.push(format!("{:<14} {value:>12.1} calories", format!("{p}th percentile")));
+    }
+
+    res.push(String::new());
+    res.push(String::from("Totals:"));
+    let most = report.histogram.iter().map(|b| b.count).max().unwrap_or(0).max(1);
+    let width = report.histogram.iter().map(|b| b.to.to_string().len()).max().unwrap_or(0);
+    for bin in &report.histogram {
+        let bar = "#".repeat((bin.count * BAR_WIDTH).div_ceil(most));
+        res.push(format!("{:>width$} - {:>width$} | {bar} {}", bin.from, bin.to, bin.count));
+    }
+
+    res.push(String::new());
+    let (low, high) = report.fences;
+    res.push(format!("Outliers, below {low:.1} or above {high:.1} calories: {}", report.outliers.len()));
+    for elf in &report.outliers {
+        res.push(format!("  elf {} with {} calories in {} items", elf.number, elf.total, elf.items));
+    }
+    res
+}
+
+fn json_spread(s: &Spread) -> String {
+    json_object(&[
+        ("min", s.min.to_string()),
+        ("max", s.max.to_string()),
+        ("mean", format!("{:.3}", s.mean)),
+        ("median", format!("{:.3}", s.median)),
+    ])
+}
+
+fn json_elf(e: &ElfStats) -> String {
+    json_object(&[("number", e.number.to_string()), ("items", e.items.to_string()), ("total", e.total.to_string())])
+}
+
+fn json(report: &Report) -> Vec<String> {
+    let percentiles: Vec<(String, String)> = report.percentiles.iter().map(|(p, v)| (p.to_string(), format!("{v:.3}"))).collect();
+    let percentiles: Vec<(&str, String)> = percentiles.iter().map(|(p, v)| (p.as_str(), v.clone())).collect();
+    let bins = report.histogram.iter().map(|b| json_object(&[("from", b.from.to_string()), ("to", b.to.to_string()), ("count", b.count.to_string())]));
+    json_fields(&[
+        ("count", report.elves.len().to_string()),
+        ("totals", json_spread(&report.totals)),
+        ("items", json_spread(&report.items)),
+        ("percentiles", json_object(&percentiles)),
+        ("histogram", json_array(bins)),
+        ("fences", json_array([format!("{:.3}", report.fences.0), format!("{:.3}", report.fences.1)])),
+        ("outliers", json_array(report.outliers.iter().map(json_elf))),
+        ("elves", json_array(report.elves.iter().map(json_elf))),
+    ])
+}
+
+// A text report, JSON with everything, or one CSV row per elf.
+pub fn format_report(format: Format, report: &Report) -> Vec<String> {
+    match format {
+        Format::Text => text(report),
+        Format::Json => json(report),
+        Format::Csv => {
+            let mut res = vec![csv_row(&["elf", "items", "total", "outlier"])];
+            res.extend(report.elves.iter().map(|e| {
+                csv_row(&[e.number.to_string(), e.items.to_string(), e.total.to_string(), is_outlier(report, e).to_string()])
+            }));
+            res
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    #[test]
+    fn test_percentile() {
+        let sorted = [1, 2, 3, 4, 10];
+        assert_eq!(3.0, percentile(&sorted, 50.0));
+        assert_eq!(2.0, percentile(&sorted, 25.0));
+        assert_eq!(7.0, percentile(&sorted, 87.5));
+        assert_eq!(10.0, percentile(&sorted, 100.0));
+        assert_eq!(2.5, percentile(&[2, 3], 50.0));
+        assert!(percentile(&[], 50.0).is_nan());
+
+        let bins = histogram(&[0, 1, 5, 9, 9], 3);
+        assert_eq!(vec![(0, 3, 2), (4, 7, 1), (8, 11, 2)], bins.iter().map(|b| (b.from, b.to, b.count)).collect::<Vec<_>>());
+        assert_eq!(vec![Bin { from: 7, to: 7, count: 2 }], histogram(&[7, 7], 4));
+    }
+
+    #[test]
+    fn test_report() {
+        let report = from_input(EXAMPLE, 4).unwrap().unwrap();
+        assert_eq!(Spread { min: 4000, max: 24000, mean: 11000.0, median: 10000.0 }, report.totals);
+        assert_eq!(Spread { min: 1, max: 3, mean: 2.0, median: 2.0 }, report.items);
+        assert_eq!((50, 10000.0), report.percentiles[2]);
+        assert_eq!(vec![2, 2, 0, 1], report.histogram.iter().map(|b| b.count).collect::<Vec<_>>());
+        assert_eq!((-1500.0, 18500.0), report.fences);
+        assert_eq!(vec![ElfStats { number: 4, items: 3, total: 24000 }], report.outliers);
+        assert!(from_input("", 4).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_elves() {
+        let expected = vec![ElfStats { number: 1, items: 2, total: 3 }, ElfStats { number: 2, items: 1, total: 3 }];
+        assert_eq!(expected, elves("1\n2\n\n\n\n3\n").unwrap());
+        let err = elves("9223372036854775807\n1\n").unwrap_err();
+        assert_eq!((2, "Total calories too large"), (err.line, err.message.as_str()));
+        assert_eq!(3, elves("1\n\n2x\n").unwrap_err().line);
+    }
+
+    #[test]
+    fn test_format() {
+        let report = from_input(EXAMPLE, 4).unwrap().unwrap();
+        let text = format_report(Format::Text, &report);
+        assert_eq!("Elves          5", text[0]);
+        assert_eq!("Median              10000.0 calories    2.0 items", text[3]);
+        assert!(text.contains(&String::from(" 4000 -  9000 | ################################################## 2")));
+        assert_eq!("  elf 4 with 24000 calories in 3 items", text.last().unwrap());
+
+        let json = format_report(Format::Json, &report).join("\n");
+        assert!(json.contains("\"totals\": {\"min\": 4000, \"max\": 24000, \"mean\": 11000.000, \"median\": 10000.000},"));
+        assert!(json.contains("\"outliers\": [{\"number\": 4, \"items\": 3, \"total\": 24000}],"));
+        let csv = format_report(Format::Csv, &report);
+        assert_eq!(vec!["elf,items,total,outlier", "1,3,6000,false"], csv[..2]);
+        assert_eq!("4,3,24000,true", csv[4]);
+    }
+}
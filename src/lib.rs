@@ -0,0 +1,38 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod difftest;
+pub mod error;
+pub mod examples;
+pub mod generate;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod interpret;
+pub mod inventory;
+pub mod output;
+pub mod pathfinding;
+pub mod point;
+pub mod reference;
+pub mod registry;
+pub mod scaffold;
+pub mod solution;
+pub mod teams;
+pub mod visualize;
+pub mod watch;
@@ -0,0 +1,195 @@
+use crate::solution::Answer;
+use std::{str::FromStr, time::Duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Invalid format: {s}, expected text, json or csv")),
+        }
+    }
+}
+
+// One solved part. `elapsed` is the time spent in the part itself, parsing excluded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut res = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(n) => n.to_string(),
+        Answer::Unsolved => String::from("null"),
+        answer => json_string(&answer.to_string()),
+    }
+}
+
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// An object on one line from fields with values that are json already.
+pub(crate) fn json_object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields.iter().map(|(name, value)| format!("{}: {value}", json_string(name))).collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+// As json_object, with a field per line.
+pub(crate) fn json_fields(fields: &[(&str, String)]) -> Vec<String> {
+    let mut res = vec![String::from("{")];
+    for (ix, (name, value)) in fields.iter().enumerate() {
+        let comma = if ix + 1 < fields.len() { "," } else { "" };
+        res.push(format!("  {}: {value}{comma}", json_string(name)));
+    }
+    res.push(String::from("}"));
+    res
+}
+
+// An array on one line.
+pub(crate) fn json_array(items: impl IntoIterator<Item = String>) -> String {
+    format!("[{}]", items.into_iter().collect::<Vec<_>>().join(", "))
+}
+
+// An array with an item per line.
+pub(crate) fn json_rows(rows: Vec<String>) -> Vec<String> {
+    if rows.is_empty() {
+        return vec![String::from("[]")];
+    }
+    let mut res = vec![String::from("[")];
+    let last = rows.len() - 1;
+    res.extend(rows.into_iter().enumerate().map(|(ix, row)| format!("  {row}{}", if ix < last { "," } else { "" })));
+    res.push(String::from("]"));
+    res
+}
+
+pub(crate) fn csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    fields.iter().map(|f| csv_field(f.as_ref())).collect::<Vec<_>>().join(",")
+}
+
+// Elapsed time is given in seconds in both json and csv. Unsolved parts have a null
+// answer in json and an empty one in csv, bitmaps are joined with newlines.
+pub fn format_records(format: Format, records: &[Record]) -> Vec<String> {
+    match format {
+        Format::Text => {
+            let mut res = vec![];
+            for (i, r) in records.iter().enumerate() {
+                if i == 0 || records[i - 1].day != r.day {
+                    res.push(format!("Day {}", r.day));
+                }
+                match r.answer {
+                    Answer::Bitmap(_) => res.push(format!("Part{}:\n{}", r.part, r.answer)),
+                    _ => res.push(format!("Part{}: {}", r.part, r.answer)),
+                }
+            }
+            res
+        }
+        Format::Json => json_rows(
+            records
+                .iter()
+                .map(|r| {
+                    json_object(&[
+                        ("day", r.day.to_string()),
+                        ("part", r.part.to_string()),
+                        ("answer", json_answer(&r.answer)),
+                        ("elapsed", format!("{:.9}", r.elapsed.as_secs_f64())),
+                    ])
+                })
+                .collect(),
+        ),
+        Format::Csv => {
+            let mut res = vec![String::from("day,part,answer,elapsed")];
+            res.extend(records.iter().map(|r| {
+                let answer = match r.answer {
+                    Answer::Unsolved => String::new(),
+                    _ => csv_field(&r.answer.to_string()),
+                };
+                format!("{},{},{},{:.9}", r.day, r.part, answer, r.elapsed.as_secs_f64())
+            }));
+            res
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        let elapsed = Duration::from_micros(1500);
+        vec![
+            Record { day: 5, part: 1, answer: Answer::Text(String::from("CMZ")), elapsed },
+            Record { day: 10, part: 2, answer: vec![String::from("#.\"")].into(), elapsed },
+            Record { day: 14, part: 1, answer: Answer::Unsolved, elapsed },
+            Record { day: 14, part: 2, answer: Answer::Int(93), elapsed },
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        let out = format_records(Format::Json, &records()).join("\n");
+        assert_eq!(
+            out,
+            "[\n  {\"day\": 5, \"part\": 1, \"answer\": \"CMZ\", \"elapsed\": 0.001500000},\n  \
+             {\"day\": 10, \"part\": 2, \"answer\": \"#.\\\"\", \"elapsed\": 0.001500000},\n  \
+             {\"day\": 14, \"part\": 1, \"answer\": null, \"elapsed\": 0.001500000},\n  \
+             {\"day\": 14, \"part\": 2, \"answer\": 93, \"elapsed\": 0.001500000}\n]"
+        );
+        assert_eq!(format_records(Format::Json, &[]), vec!["[]"]);
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            format_records(Format::Csv, &records()),
+            vec![
+                "day,part,answer,elapsed",
+                "5,1,CMZ,0.001500000",
+                "10,2,\"#.\"\"\",0.001500000",
+                "14,1,,0.001500000",
+                "14,2,93,0.001500000",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            format_records(Format::Text, &records()),
+            vec!["Day 5", "Part1: CMZ", "Day 10", "Part2:\n#.\"", "Day 14", "Part1: not solved", "Part2: 93"]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    // The nodes reachable in one step from `node` and the cost of getting there.
+    fn neighbours(&self, node: &Self::Node) -> Vec<(Self::Node, usize)>;
+
+    // A lower bound of the cost from `node` to `goal`, used by `astar`. It must never
+    // overestimate, the default of 0 turns A* into Dijkstra.
+    fn heuristic(&self, _node: &Self::Node, _goal: &Self::Node) -> usize {
+        0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: usize,
+    pub nodes: Vec<N>,
+}
+
+// Distances from the start nodes and the node each one was reached from.
+#[derive(Debug, Clone)]
+pub struct Search<N> {
+    pub dist: HashMap<N, usize>,
+    pub prev: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash> Search<N> {
+    fn new() -> Self {
+        Self { dist: HashMap::new(), prev: HashMap::new() }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.dist.get(node).copied()
+    }
+
+    // The path from the start node it was reached from to `to`, both included.
+    pub fn path(&self, to: &N) -> Option<Path<N>> {
+        let cost = self.distance(to)?;
+        let mut nodes = vec![to.clone()];
+        while let Some(prev) = self.prev.get(nodes.last().unwrap()) {
+            nodes.push(prev.clone());
+        }
+        nodes.reverse();
+        Some(Path { cost, nodes })
+    }
+}
+
+// Breadth first search from all of `starts` at once, every edge counts as one step.
+pub fn bfs_multi<G: Graph>(graph: &G, starts: impl IntoIterator<Item = G::Node>) -> Search<G::Node> {
+    let mut search = Search::new();
+    let mut to_visit = VecDeque::new();
+    for start in starts {
+        if search.dist.insert(start.clone(), 0).is_none() {
+            to_visit.push_back((start, 0));
+        }
+    }
+    while let Some((node, dist)) = to_visit.pop_front() {
+        for (next, _) in graph.neighbours(&node) {
+            if !search.dist.contains_key(&next) {
+                search.dist.insert(next.clone(), dist + 1);
+                search.prev.insert(next.clone(), node.clone());
+                to_visit.push_back((next, dist + 1));
+            }
+        }
+    }
+    search
+}
+
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> Search<G::Node> {
+    bfs_multi(graph, [start])
+}
+
+struct Entry<N> {
+    cost: usize,
+    node: N,
+}
+
+impl<N> PartialEq for Entry<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl<N> Eq for Entry<N> {}
+
+impl<N> Ord for Entry<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+impl<N> PartialOrd for Entry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Lowest cost search, stopping once `goal` is settled if one is given.
+pub fn dijkstra<G: Graph>(graph: &G, start: G::Node, goal: Option<&G::Node>) -> Search<G::Node> {
+    let mut search = Search::new();
+    let mut queue = BinaryHeap::new();
+    search.dist.insert(start.clone(), 0);
+    queue.push(Entry { cost: 0, node: start });
+
+    while let Some(Entry { cost, node }) = queue.pop() {
+        if cost > search.dist[&node] {
+            continue;
+        }
+        if goal == Some(&node) {
+            break;
+        }
+        for (next, step) in graph.neighbours(&node) {
+            let next_cost = cost + step;
+            if search.dist.get(&next).is_none_or(|d| next_cost < *d) {
+                search.dist.insert(next.clone(), next_cost);
+                search.prev.insert(next.clone(), node.clone());
+                queue.push(Entry { cost: next_cost, node: next });
+            }
+        }
+    }
+    search
+}
+
+pub fn astar<G: Graph>(graph: &G, start: G::Node, goal: &G::Node) -> Option<Path<G::Node>> {
+    let mut search = Search::new();
+    let mut queue = BinaryHeap::new();
+    search.dist.insert(start.clone(), 0);
+    queue.push(Entry { cost: graph.heuristic(&start, goal), node: start });
+
+    while let Some(Entry { cost, node }) = queue.pop() {
+        let dist = search.dist[&node];
+        if cost > dist + graph.heuristic(&node, goal) {
+            continue;
+        }
+        if &node == goal {
+            return search.path(goal);
+        }
+        for (next, step) in graph.neighbours(&node) {
+            let next_dist = dist + step;
+            if search.dist.get(&next).is_none_or(|d| next_dist < *d) {
+                search.dist.insert(next.clone(), next_dist);
+                search.prev.insert(next.clone(), node.clone());
+                queue.push(Entry { cost: next_dist + graph.heuristic(&next, goal), node: next });
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Weighted edges a -1-> b -1-> c -5-> d and a -10-> d, e is unreachable.
+    struct Small;
+
+    impl Graph for Small {
+        type Node = char;
+
+        fn neighbours(&self, node: &char) -> Vec<(char, usize)> {
+            match node {
+                'a' => vec![('b', 1), ('d', 10)],
+                'b' => vec![('c', 1)],
+                'c' => vec![('d', 5)],
+                _ => vec![],
+            }
+        }
+    }
+
+    // An open 2D plane, the Manhattan distance is an exact heuristic.
+    struct Plane;
+
+    impl Graph for Plane {
+        type Node = (i32, i32);
+
+        fn neighbours(&self, &(x, y): &(i32, i32)) -> Vec<((i32, i32), usize)> {
+            vec![((x + 1, y), 1), ((x - 1, y), 1), ((x, y + 1), 1), ((x, y - 1), 1)]
+        }
+
+        fn heuristic(&self, node: &(i32, i32), goal: &(i32, i32)) -> usize {
+            ((node.0 - goal.0).abs() + (node.1 - goal.1).abs()) as usize
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs(&Small, 'a');
+        assert_eq!(Some(1), search.distance(&'d'));
+        assert_eq!(Some(2), search.distance(&'c'));
+        assert_eq!(None, search.distance(&'e'));
+        assert_eq!(vec!['a', 'b', 'c'], search.path(&'c').unwrap().nodes);
+
+        let search = bfs_multi(&Small, ['b', 'c']);
+        assert_eq!(Some(0), search.distance(&'c'));
+        assert_eq!(vec!['c', 'd'], search.path(&'d').unwrap().nodes);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra(&Small, 'a', None);
+        assert_eq!(Path { cost: 7, nodes: vec!['a', 'b', 'c', 'd'] }, search.path(&'d').unwrap());
+        assert_eq!(None, search.path(&'e'));
+    }
+
+    #[test]
+    fn test_astar() {
+        let path = astar(&Plane, (0, 0), &(3, -2)).unwrap();
+        assert_eq!(5, path.cost);
+        assert_eq!(6, path.nodes.len());
+        assert_eq!(Some(&(3, -2)), path.nodes.last());
+        assert_eq!(Some(7), astar(&Small, 'a', &'d').map(|p| p.cost));
+        assert_eq!(None, astar(&Small, 'a', &'e'));
+    }
+}
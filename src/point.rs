@@ -0,0 +1,334 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+// The signed integer types points can be built from.
+pub trait Coord:
+    Copy + Debug + Default + Ord + Hash + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(impl Coord for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        })*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(T::ZERO, T::ZERO)
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    // Quarter turns around the origin with y pointing up, so left is counterclockwise.
+    pub fn rotate_left(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    pub fn rotate_right(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Dir::ALL.into_iter().map(move |d| self + d.delta())
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |d| self + d.delta())
+    }
+}
+
+impl Point2<i32> {
+    // Grid cells are (row, column), rows become y and columns x.
+    pub fn from_cell((row, col): (usize, usize)) -> Self {
+        Self::new(col as i32, row as i32)
+    }
+
+    pub fn to_cell(self) -> (usize, usize) {
+        (self.y as usize, self.x as usize)
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(T::ZERO, T::ZERO, T::ZERO)
+    }
+
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs()).max((self.z - other.z).abs())
+    }
+
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    // The six points sharing a face with this one.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        let (o, z) = (T::ONE, T::ZERO);
+        [(o, z, z), (-o, z, z), (z, o, z), (z, -o, z), (z, z, o), (z, z, -o)]
+            .into_iter()
+            .map(move |(dx, dy, dz)| self + Self::new(dx, dy, dz))
+    }
+}
+
+macro_rules! impl_ops {
+    ($p:ident { $($f:ident),* }) => {
+        impl<T: Coord> Add for $p<T> {
+            type Output = Self;
+
+            fn add(self, o: Self) -> Self {
+                Self { $($f: self.$f + o.$f),* }
+            }
+        }
+
+        impl<T: Coord> Sub for $p<T> {
+            type Output = Self;
+
+            fn sub(self, o: Self) -> Self {
+                Self { $($f: self.$f - o.$f),* }
+            }
+        }
+
+        impl<T: Coord> Neg for $p<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($f: -self.$f),* }
+            }
+        }
+
+        impl<T: Coord> Mul<T> for $p<T> {
+            type Output = Self;
+
+            fn mul(self, n: T) -> Self {
+                Self { $($f: self.$f * n),* }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $p<T> {
+            fn add_assign(&mut self, o: Self) {
+                *self = *self + o;
+            }
+        }
+
+        impl<T: Coord> SubAssign for $p<T> {
+            fn sub_assign(&mut self, o: Self) {
+                *self = *self - o;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+// The four axis directions, Up is +y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    pub fn delta<T: Coord>(self) -> Point2<T> {
+        let (o, z) = (T::ONE, T::ZERO);
+        match self {
+            Dir::Up => Point2::new(z, o),
+            Dir::Right => Point2::new(o, z),
+            Dir::Down => Point2::new(z, -o),
+            Dir::Left => Point2::new(-o, z),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+// The eight compass directions, clockwise from North which is +y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [Dir8::N, Dir8::NE, Dir8::E, Dir8::SE, Dir8::S, Dir8::SW, Dir8::W, Dir8::NW];
+
+    pub fn delta<T: Coord>(self) -> Point2<T> {
+        let (o, z) = (T::ONE, T::ZERO);
+        let (x, y) = match self {
+            Dir8::N => (z, o),
+            Dir8::NE => (o, o),
+            Dir8::E => (o, z),
+            Dir8::SE => (o, -o),
+            Dir8::S => (z, -o),
+            Dir8::SW => (-o, -o),
+            Dir8::W => (-o, z),
+            Dir8::NW => (-o, o),
+        };
+        Point2::new(x, y)
+    }
+}
+
+// The smallest axis aligned rectangle holding a set of points, both corners inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds2<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coord> Bounds2<T> {
+    pub fn new(p: Point2<T>) -> Self {
+        Self { min: p, max: p }
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = Point2<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = Self::new(points.next()?);
+        Some(points.fold(first, |b, p| b.extend(p)))
+    }
+
+    pub fn extend(self, p: Point2<T>) -> Self {
+        Self {
+            min: Point2::new(self.min.x.min(p.x), self.min.y.min(p.y)),
+            max: Point2::new(self.max.x.max(p.x), self.max.y.max(p.y)),
+        }
+    }
+
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point2() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 4);
+        assert_eq!(Point2::new(-2, 2), a + b);
+        assert_eq!(Point2::new(4, -6), a - b);
+        assert_eq!(Point2::new(3, -6), a * 3);
+        assert_eq!(10, a.manhattan(b));
+        assert_eq!(6, a.chebyshev(b));
+        assert_eq!(Point2::new(-1, 1), b.signum());
+        assert_eq!(Point2::new(2, 1), a.rotate_left());
+        assert_eq!(a, a.rotate_left().rotate_right());
+        assert_eq!(-a, a.rotate_right().rotate_right());
+        assert_eq!(4, Point2::<i64>::zero().neighbours4().filter(|p| p.manhattan(Point2::zero()) == 1).count());
+        assert_eq!(8, Point2::<i64>::zero().neighbours8().filter(|p| p.chebyshev(Point2::zero()) == 1).count());
+        assert_eq!((2, 1), Point2::from_cell((2, 1)).to_cell());
+    }
+
+    #[test]
+    fn test_point3() {
+        let a = Point3::new(1, 2, 3);
+        assert_eq!(Point3::new(0, 0, 0), a - a);
+        assert_eq!(6, a.manhattan(Point3::zero()));
+        assert_eq!(3, a.chebyshev(Point3::zero()));
+        assert!(a.neighbours6().all(|p| p.manhattan(a) == 1));
+    }
+
+    #[test]
+    fn test_dirs_and_bounds() {
+        assert_eq!(Dir::Left, Dir::Up.turn_left());
+        assert_eq!(Dir::Up, Dir::Left.turn_right());
+        assert_eq!(Dir::Down, Dir::Up.opposite());
+        for d in Dir::ALL {
+            assert_eq!(d.delta::<i32>().rotate_right(), d.turn_right().delta());
+        }
+        assert_eq!(Point2::new(1, -1), Dir8::SE.delta::<i32>());
+
+        let b = Bounds2::from_points([Point2::new(2, 3), Point2::new(-1, 5), Point2::new(0, 0)]).unwrap();
+        assert_eq!(Bounds2 { min: Point2::new(-1, 0), max: Point2::new(2, 5) }, b);
+        assert_eq!((4, 6), (b.width(), b.height()));
+        assert!(b.contains(Point2::new(0, 5)));
+        assert!(!b.contains(Point2::new(3, 0)));
+        assert_eq!(None, Bounds2::<i32>::from_points([]));
+    }
+}
@@ -0,0 +1,747 @@
+// Slow but straightforward solvers for checking the real ones, see `difftest`.
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{
+    day10, day11, day12, day13, day14, day15, day16, day3, day4, day5, day7, day8, day9,
+    error::ParseError,
+    solution::{Answer, DynSolution, Solution},
+};
+
+pub fn solvers() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        Box::new(Day1),
+        Box::new(Day2),
+        Box::new(Day3),
+        Box::new(Day4),
+        Box::new(Day5),
+        Box::new(Day6),
+        Box::new(Day7::default()),
+        Box::new(Day8),
+        Box::new(Day9),
+        Box::new(Day10),
+        Box::new(Day11),
+        Box::new(Day12),
+        Box::new(Day13),
+        Box::new(Day14),
+        Box::new(Day15::default()),
+        Box::new(Day16),
+    ]
+}
+
+pub fn solver(day: u32) -> Option<Box<dyn DynSolution>> {
+    solvers().into_iter().find(|s| s.day() == day)
+}
+
+fn lines(indata: &str) -> impl Iterator<Item = &str> {
+    indata.lines().filter(|l| !l.trim().is_empty())
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Input = Vec<i64>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        let blocks = crate::day1::parse_indata(indata)?;
+        Ok(blocks.iter().map(|b| b.iter().sum()).collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        input.iter().copied().max().unwrap_or(0).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let mut sums = input.clone();
+        let mut total = 0;
+        for _ in 0..3 {
+            if let Some((ix, sum)) = sums.iter().enumerate().max_by_key(|(_, s)| **s) {
+                total += sum;
+                sums.remove(ix);
+            }
+        }
+        total.into()
+    }
+}
+
+pub struct Day2;
+
+impl Day2 {
+    fn score(input: &[String], table: [(&str, i64); 9]) -> Answer {
+        let table: HashMap<&str, i64> = table.into_iter().collect();
+        input.iter().map(|l| table[l.as_str()]).sum::<i64>().into()
+    }
+}
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Input = Vec<String>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        crate::day2::parse_indata(&crate::day2::Game::default(), indata)?;
+        Ok(lines(indata).map(String::from).collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let table = [
+            ("A X", 1 + 3), ("A Y", 2 + 6), ("A Z", 3),
+            ("B X", 1), ("B Y", 2 + 3), ("B Z", 3 + 6),
+            ("C X", 1 + 6), ("C Y", 2), ("C Z", 3 + 3),
+        ];
+        Self::score(input, table)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let table = [
+            ("A X", 3), ("A Y", 1 + 3), ("A Z", 2 + 6),
+            ("B X", 1), ("B Y", 2 + 3), ("B Z", 3 + 6),
+            ("C X", 2), ("C Y", 3 + 3), ("C Z", 1 + 6),
+        ];
+        Self::score(input, table)
+    }
+}
+
+pub struct Day3;
+
+fn priority(c: char) -> i64 {
+    let items = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    items.find(c).map_or(0, |ix| ix as i64 + 1)
+}
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Input = Vec<String>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day3::parse_indata(indata)?;
+        Ok(lines(indata).map(String::from).collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let mut sum = 0;
+        for l in input {
+            let (left, right) = l.split_at(l.len() / 2);
+            if let Some(c) = left.chars().find(|c| right.contains(*c)) {
+                sum += priority(c);
+            }
+        }
+        sum.into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let mut sum = 0;
+        for group in input.chunks_exact(3) {
+            if let Some(c) = group[0].chars().find(|c| group[1].contains(*c) && group[2].contains(*c)) {
+                sum += priority(c);
+            }
+        }
+        sum.into()
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Input = Vec<(HashSet<i32>, HashSet<i32>)>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        let pairs = day4::parse_indata(indata)?;
+        Ok(pairs.into_iter().map(|(a, b, c, d)| ((a..=b).collect(), (c..=d).collect())).collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        input.iter().filter(|(a, b)| a.is_subset(b) || b.is_subset(a)).count().into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        input.iter().filter(|(a, b)| !a.is_disjoint(b)).count().into()
+    }
+}
+
+pub struct Day5;
+
+impl Day5 {
+    fn play(input: &day5::InData, reverse: bool) -> Answer {
+        let (mut stacks, commands) = input.clone();
+        for &(count, from, to) in &commands {
+            let at = stacks[from - 1].len() - count.min(stacks[from - 1].len());
+            let mut moved = stacks[from - 1].split_off(at);
+            if reverse {
+                moved.reverse();
+            }
+            stacks[to - 1].extend(moved);
+        }
+        stacks.iter().filter_map(|s| s.last()).collect::<String>().into()
+    }
+}
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Input = day5::InData;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day5::parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        Self::play(input, true)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        Self::play(input, false)
+    }
+}
+
+pub struct Day6;
+
+impl Day6 {
+    fn marker(input: &[char], n: usize) -> Answer {
+        for end in n..=input.len() {
+            let window = &input[end - n..end];
+            if (0..n).all(|i| (i + 1..n).all(|j| window[i] != window[j])) {
+                return end.into();
+            }
+        }
+        Answer::Unsolved
+    }
+}
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Input = Vec<char>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        Ok(indata.trim_end().chars().collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        Self::marker(input, 4)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        Self::marker(input, 14)
+    }
+}
+
+// Takes the puzzle parameters from the real solver so they share the options.
+#[derive(Default)]
+pub struct Day7(pub day7::Day7);
+
+impl Day7 {
+    // The size of every directory, found by adding up the files below it.
+    fn dir_sizes(input: &[(String, usize)], dirs: &HashSet<String>) -> Vec<usize> {
+        dirs.iter()
+            .map(|dir| input.iter().filter(|(path, _)| path.starts_with(dir.as_str())).map(|(_, size)| size).sum())
+            .collect()
+    }
+}
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    type Input = (Vec<(String, usize)>, HashSet<String>);
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day7::parse_indata(indata)?;
+        let mut cwd: Vec<&str> = vec![];
+        let mut files = HashMap::new();
+        let mut dirs = HashSet::new();
+        for l in lines(indata) {
+            let words: Vec<&str> = l.split_whitespace().collect();
+            match words[..] {
+                ["$", "cd", ".."] => {
+                    cwd.pop();
+                }
+                ["$", "cd", dir] => {
+                    cwd.push(dir);
+                    dirs.insert(cwd.join("/") + "/");
+                }
+                ["$", "ls"] | ["dir", _] => {}
+                [size, name] => {
+                    files.insert(format!("{}/{name}", cwd.join("/")), size.parse().unwrap());
+                }
+                _ => {}
+            }
+        }
+        Ok((files.into_iter().collect(), dirs))
+    }
+
+    fn part1(&self, (files, dirs): &Self::Input) -> Answer {
+        Self::dir_sizes(files, dirs).into_iter().filter(|s| *s <= self.0.limit).sum::<usize>().into()
+    }
+
+    fn part2(&self, (files, dirs): &Self::Input) -> Answer {
+        let used: usize = files.iter().map(|(_, size)| size).sum();
+        let free = self.0.disk_size as i64 - used as i64;
+        let missing = self.0.needed as i64 - free;
+        match Self::dir_sizes(files, dirs).into_iter().filter(|s| *s as i64 >= missing).min() {
+            Some(size) => size.into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn options(&self) -> Vec<(&'static str, String)> {
+        Solution::options(&self.0)
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        Solution::set_option(&mut self.0, name, value)
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day8::parse_indata(indata)?;
+        Ok(lines(indata).map(|l| l.chars().map(|c| c.to_digit(10).unwrap() as i32).collect()).collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let (rows, cols) = (input.len(), input[0].len());
+        let mut count = 0;
+        for r in 0..rows {
+            for c in 0..cols {
+                let h = input[r][c];
+                let visible = (0..r).all(|i| input[i][c] < h)
+                    || (r + 1..rows).all(|i| input[i][c] < h)
+                    || (0..c).all(|i| input[r][i] < h)
+                    || (c + 1..cols).all(|i| input[r][i] < h);
+                if visible {
+                    count += 1;
+                }
+            }
+        }
+        count.into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let (rows, cols) = (input.len() as i32, input[0].len() as i32);
+        let mut best = 0;
+        for r in 0..rows {
+            for c in 0..cols {
+                let mut score = 1;
+                for (dr, dc) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                    let (mut i, mut j, mut seen) = (r + dr, c + dc, 0);
+                    while i >= 0 && j >= 0 && i < rows && j < cols {
+                        seen += 1;
+                        if input[i as usize][j as usize] >= input[r as usize][c as usize] {
+                            break;
+                        }
+                        i += dr;
+                        j += dc;
+                    }
+                    score *= seen;
+                }
+                best = best.max(score);
+            }
+        }
+        best.into()
+    }
+}
+
+pub struct Day9;
+
+impl Day9 {
+    fn visited(input: &[(i32, i32, i32)], knots: usize) -> Answer {
+        let mut rope = vec![(0, 0); knots];
+        let mut visited = HashSet::from([(0, 0)]);
+        for &(dx, dy, steps) in input {
+            for _ in 0..steps {
+                rope[0] = (rope[0].0 + dx, rope[0].1 + dy);
+                for k in 1..knots {
+                    let (hx, hy) = rope[k - 1];
+                    let (tx, ty) = &mut rope[k];
+                    if (hx - *tx).abs() > 1 || (hy - *ty).abs() > 1 {
+                        *tx += (hx - *tx).clamp(-1, 1);
+                        *ty += (hy - *ty).clamp(-1, 1);
+                    }
+                }
+                visited.insert(rope[knots - 1]);
+            }
+        }
+        visited.len().into()
+    }
+}
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Input = Vec<(i32, i32, i32)>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        let commands = day9::parse_indata(indata)?;
+        Ok(commands
+            .iter()
+            .map(|c| {
+                let d = c.dir.delta::<i32>();
+                (d.x, d.y, c.steps)
+            })
+            .collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        Self::visited(input, 2)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        Self::visited(input, 10)
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    // The value of X during each cycle.
+    type Input = Vec<i32>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day10::parse_indata(indata)?;
+        let mut x = 1;
+        let mut during = vec![];
+        for l in lines(indata) {
+            during.push(x);
+            if let Some(value) = l.strip_prefix("addx ") {
+                during.push(x);
+                x += value.parse::<i32>().unwrap();
+            }
+        }
+        Ok(during)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        [20, 60, 100, 140, 180, 220]
+            .iter()
+            .filter(|cycle| **cycle <= input.len())
+            .map(|cycle| *cycle as i32 * input[cycle - 1])
+            .sum::<i32>()
+            .into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let rows: Vec<String> = input
+            .chunks_exact(40)
+            .map(|row| row.iter().enumerate().map(|(col, x)| if (x - col as i32).abs() <= 1 { '#' } else { '.' }).collect())
+            .collect();
+        rows.into()
+    }
+}
+
+pub struct Day11;
+
+impl Day11 {
+    fn business(mut inspections: Vec<i64>) -> Answer {
+        inspections.sort_unstable_by(|a, b| b.cmp(a));
+        inspections.iter().take(2).product::<i64>().into()
+    }
+
+    fn apply(op: &day11::Op, worry: i128) -> i128 {
+        match op {
+            day11::Op::Add(n) => worry + *n as i128,
+            day11::Op::Mul(n) => worry * *n as i128,
+            day11::Op::Sq => worry * worry,
+            day11::Op::X2 => worry + worry,
+        }
+    }
+}
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = day11::InData;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day11::parse_indata(indata)
+    }
+
+    // Plain worry levels, no modulo.
+    fn part1(&self, (monkeys, items): &Self::Input) -> Answer {
+        let ix: HashMap<i64, usize> = monkeys.iter().enumerate().map(|(i, m)| (m.id, i)).collect();
+        let mut held: Vec<Vec<i128>> = monkeys.iter().map(|m| items[&m.id].iter().map(|w| *w as i128).collect()).collect();
+        let mut inspections = vec![0; monkeys.len()];
+        for _ in 0..20 {
+            for (i, m) in monkeys.iter().enumerate() {
+                for worry in std::mem::take(&mut held[i]) {
+                    inspections[i] += 1;
+                    let worry = Self::apply(&m.op, worry) / 3;
+                    let to = if worry % m.div_by as i128 == 0 { m.monkey_iftrue } else { m.monkey_iffalse };
+                    held[ix[&to]].push(worry);
+                }
+            }
+        }
+        Self::business(inspections)
+    }
+
+    // Every item keeps its worry level modulo each divisor separately instead of modulo
+    // their product.
+    fn part2(&self, (monkeys, items): &Self::Input) -> Answer {
+        let ix: HashMap<i64, usize> = monkeys.iter().enumerate().map(|(i, m)| (m.id, i)).collect();
+        let residues = |w: i64| -> Vec<i128> { monkeys.iter().map(|m| (w % m.div_by) as i128).collect() };
+        let mut held: Vec<Vec<Vec<i128>>> = monkeys.iter().map(|m| items[&m.id].iter().map(|w| residues(*w)).collect()).collect();
+        let mut inspections = vec![0; monkeys.len()];
+        for _ in 0..10_000 {
+            for (i, m) in monkeys.iter().enumerate() {
+                for worry in std::mem::take(&mut held[i]) {
+                    inspections[i] += 1;
+                    let worry: Vec<i128> = worry
+                        .iter()
+                        .zip(monkeys)
+                        .map(|(r, d)| Self::apply(&m.op, *r) % d.div_by as i128)
+                        .collect();
+                    let to = if worry[i] == 0 { m.monkey_iftrue } else { m.monkey_iffalse };
+                    held[ix[&to]].push(worry);
+                }
+            }
+        }
+        Self::business(inspections)
+    }
+}
+
+pub struct Day12;
+
+impl Day12 {
+    // Steps to E from every cell, relaxed until nothing changes.
+    fn steps_to_end(heights: &[Vec<i32>], end: (usize, usize)) -> Vec<Vec<Option<usize>>> {
+        let (rows, cols) = (heights.len(), heights[0].len());
+        let mut steps = vec![vec![None; cols]; rows];
+        steps[end.0][end.1] = Some(0);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for r in 0..rows {
+                for c in 0..cols {
+                    let next = [(r.wrapping_sub(1), c), (r + 1, c), (r, c.wrapping_sub(1)), (r, c + 1)]
+                        .into_iter()
+                        .filter(|(nr, nc)| *nr < rows && *nc < cols && heights[*nr][*nc] - heights[r][c] <= 1)
+                        .filter_map(|(nr, nc)| steps[nr][nc].map(|s: usize| s + 1))
+                        .min();
+                    if next.is_some() && (steps[r][c].is_none() || next < steps[r][c]) {
+                        steps[r][c] = next;
+                        changed = true;
+                    }
+                }
+            }
+        }
+        steps
+    }
+}
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input = (Vec<Vec<i32>>, (usize, usize), Vec<Vec<Option<usize>>>);
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day12::parse_maze(indata)?;
+        let heights: Vec<Vec<i32>> = lines(indata)
+            .map(|l| l.chars().map(|c| match c { 'S' => 0, 'E' => 25, c => c as i32 - 'a' as i32 }).collect())
+            .collect();
+        let find = |ch: char| lines(indata).enumerate().find_map(|(r, l)| l.find(ch).map(|c| (r, c))).unwrap();
+        let steps = Self::steps_to_end(&heights, find('E'));
+        Ok((heights, find('S'), steps))
+    }
+
+    fn part1(&self, (_, start, steps): &Self::Input) -> Answer {
+        steps[start.0][start.1].map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn part2(&self, (heights, _, steps): &Self::Input) -> Answer {
+        let lowest = heights.iter().flatten().zip(steps.iter().flatten()).filter(|(h, _)| **h == 0);
+        lowest.filter_map(|(_, s)| *s).min().map_or(Answer::Unsolved, Answer::from)
+    }
+}
+
+pub struct Day13;
+
+impl Day13 {
+    fn compare(a: &day13::Item, b: &day13::Item) -> Ordering {
+        use day13::Item::{Int, List};
+        match (a, b) {
+            (Int(x), Int(y)) => x.cmp(y),
+            (Int(x), List(_)) => Self::compare(&List(vec![Int(*x)]), b),
+            (List(_), Int(y)) => Self::compare(a, &List(vec![Int(*y)])),
+            (List(xs), List(ys)) => {
+                for (x, y) in xs.iter().zip(ys) {
+                    match Self::compare(x, y) {
+                        Ordering::Equal => {}
+                        order => return order,
+                    }
+                }
+                xs.len().cmp(&ys.len())
+            }
+        }
+    }
+}
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input = Vec<Vec<day13::Item>>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day13::parse_indata(indata)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let ordered = input.iter().enumerate().filter(|(_, p)| Self::compare(&p[0], &p[1]) == Ordering::Less);
+        ordered.map(|(ix, _)| ix + 1).sum::<usize>().into()
+    }
+
+    // A divider's position is one more than the number of packets sorting before it.
+    fn part2(&self, input: &Self::Input) -> Answer {
+        use day13::Item::{Int, List};
+        let dividers = [List(vec![List(vec![Int(2)])]), List(vec![List(vec![Int(6)])])];
+        let before = |d: &day13::Item| input.iter().flatten().filter(|p| Self::compare(p, d) == Ordering::Less).count();
+        ((before(&dividers[0]) + 1) * (before(&dividers[1]) + 2)).into()
+    }
+}
+
+pub struct Day14;
+
+impl Day14 {
+    // Grains that come to rest, stopping when one falls past `abyss` or the source is blocked.
+    fn pour(rocks: &HashSet<(i32, i32)>, floor: Option<i32>, abyss: i32) -> usize {
+        let mut blocked = rocks.clone();
+        let mut grains = 0;
+        while !blocked.contains(&(500, 0)) {
+            let (mut x, mut y) = (500, 0);
+            loop {
+                let next = [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+                    .into_iter()
+                    .find(|p| !blocked.contains(p) && Some(p.1) != floor);
+                match next {
+                    Some(p) => (x, y) = p,
+                    None => break,
+                }
+                if y > abyss {
+                    return grains;
+                }
+            }
+            blocked.insert((x, y));
+            grains += 1;
+        }
+        grains
+    }
+}
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = HashSet<(i32, i32)>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        let mut rocks = HashSet::new();
+        for path in day14::parse_indata(indata)? {
+            for w in path.windows(2) {
+                for x in w[0].x.min(w[1].x)..=w[0].x.max(w[1].x) {
+                    for y in w[0].y.min(w[1].y)..=w[0].y.max(w[1].y) {
+                        rocks.insert((x, y));
+                    }
+                }
+            }
+        }
+        Ok(rocks)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let y_max = input.iter().map(|p| p.1).max().unwrap_or(0);
+        Self::pour(input, None, y_max).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let floor = input.iter().map(|p| p.1).max().unwrap_or(0) + 2;
+        Self::pour(input, Some(floor), floor).into()
+    }
+}
+
+// Checks every position, which is only practical with a small --row and --max-coord.
+#[derive(Default)]
+pub struct Day15(pub day15::Day15);
+
+impl Day15 {
+    fn covered(sensors: &[((i64, i64), i64)], x: i64, y: i64) -> bool {
+        sensors.iter().any(|((sx, sy), r)| (sx - x).abs() + (sy - y).abs() <= *r)
+    }
+}
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Input = (Vec<((i64, i64), i64)>, HashSet<(i64, i64)>);
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        let zone = day15::parse_indata(indata)?;
+        let sensors = zone.sensors.iter().map(|s| ((s.sensor.x, s.sensor.y), s.sensor.manhattan(s.beacon))).collect();
+        Ok((sensors, zone.sensors.iter().map(|s| (s.beacon.x, s.beacon.y)).collect()))
+    }
+
+    fn part1(&self, (sensors, beacons): &Self::Input) -> Answer {
+        let y = self.0.row;
+        let reach = sensors.iter().map(|((sx, _), r)| (sx - r, sx + r));
+        let (left, right) = reach.fold((i64::MAX, i64::MIN), |a, (l, r)| (a.0.min(l), a.1.max(r)));
+        (left..=right).filter(|x| Self::covered(sensors, *x, y) && !beacons.contains(&(*x, y))).count().into()
+    }
+
+    fn part2(&self, (sensors, _): &Self::Input) -> Answer {
+        for y in 0..=self.0.max_coord {
+            for x in 0..=self.0.max_coord {
+                if !Self::covered(sensors, x, y) {
+                    return (x * 4000000 + y).into();
+                }
+            }
+        }
+        Answer::Unsolved
+    }
+
+    fn options(&self) -> Vec<(&'static str, String)> {
+        Solution::options(&self.0)
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        Solution::set_option(&mut self.0, name, value)
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = ();
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        day16::parse_indata(indata).map(|_| ())
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_release_answers() {
+        // The examples of the puzzles, where the answers are known.
+        let examples = [
+            (1, "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n", 24000, 45000),
+            (2, "A Y\nB X\nC Z\n", 15, 12),
+            (6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n", 7, 19),
+            (14, "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n", 24, 93),
+        ];
+        for (day, indata, part1, part2) in examples {
+            let s = solver(day).unwrap();
+            assert_eq!(Ok(Answer::Int(part1)), s.solve(indata, 1), "day {day}");
+            assert_eq!(Ok(Answer::Int(part2)), s.solve(indata, 2), "day {day}");
+        }
+        assert!(solvers().iter().all(|s| registry::solver(s.day()).is_some()));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{solution::DynSolution, *};
+
+pub fn solvers() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        Box::new(day1::Day1::default()),
+        Box::new(day2::Day2::default()),
+        Box::new(day3::Day3),
+        Box::new(day4::Day4),
+        Box::new(day5::Day5),
+        Box::new(day6::Day6),
+        Box::new(day7::Day7::default()),
+        Box::new(day8::Day8),
+        Box::new(day9::Day9),
+        Box::new(day10::Day10),
+        Box::new(day11::Day11),
+        Box::new(day12::Day12),
+        Box::new(day13::Day13),
+        Box::new(day14::Day14),
+        Box::new(day15::Day15::default()),
+        Box::new(day16::Day16),
+    ]
+}
+
+pub fn solver(day: u32) -> Option<Box<dyn DynSolution>> {
+    solvers().into_iter().find(|s| s.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Answer;
+
+    #[test]
+    fn test_lookup() {
+        assert!(solvers().iter().enumerate().all(|(ix, s)| s.day() == ix as u32 + 1));
+        assert_eq!(12, solver(12).unwrap().day());
+        assert!(solver(0).is_none());
+        assert_eq!(Ok(Answer::Unsolved), solver(16).unwrap().solve("Valve AA has flow rate=0; tunnel leads to valve AA\n", 1));
+        assert_eq!(Ok(Answer::Int(24000)), solver(1).unwrap().solve("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n", 1));
+    }
+
+    #[test]
+    fn test_options() {
+        let mut s = solver(7).unwrap();
+        assert_eq!("70000000", s.options()[1].1);
+        s.set_option("limit", "5").unwrap();
+        assert_eq!(Ok(Answer::Int(0)), s.solve("$ cd /\n$ ls\n10 a\n", 1));
+        assert!(solver(1).unwrap().set_option("limit", "5").is_err());
+    }
+
+    #[test]
+    fn test_line_endings_and_whitespace() {
+        for day in 1..=16 {
+            let (s, _) = difftest::solvers(day).unwrap();
+            let indata = difftest::generate_input(day, 1, 10).unwrap();
+            let variants = [
+                indata.replace('\n', "\r\n"),
+                indata.replace('\n', " \n"),
+                indata.trim_end().to_string(),
+                format!("{indata}\n\n"),
+            ];
+            for part in 1..=2 {
+                let answer = s.solve(&indata, part);
+                for variant in &variants {
+                    assert_eq!(answer, s.solve(variant, part), "day {day} part {part}: {variant:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const SOLVER: &str = r##"use crate::{
+    error::{ParseError, Source},
+    solution::{Answer, Solution},
+};
+
+pub fn parse_indata(indata: &str) -> Result<Vec<String>, ParseError> {
+    let src = Source::new(Day{N}::DAY, indata);
+    Ok(src.lines().map(String::from).collect())
+}
+
+pub struct Day{N};
+
+impl Solution for Day{N} {
+    const DAY: u32 = {N};
+    type Input = Vec<String>;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+        parse_indata(indata)
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_DATA: &str = indoc! {r#"
+    example
+    "#
+    };
+
+    #[test]
+    fn test_part1() {
+        let input = parse_indata(TEST_DATA).unwrap();
+        assert_eq!(1, input.len());
+        assert_eq!(Answer::Unsolved, Day{N}.part1(&input));
+    }
+}
+"##;
+
+const BINARY: &str = r#"use advent_of_code_2022::{day{N}::*, input, solution::Solution};
+
+fn main() {
+    let indata = input::from_args({N});
+    let data = input::or_exit(parse_indata(&indata));
+    println!("Part1: {}", Day{N}.part1(&data));
+    println!("Part2: {}", Day{N}.part2(&data));
+}
+"#;
+
+const FIXTURE: &str = "# Add the answers as part1 and part2, or replace this file with 'aoc extract {N} <page.html> --force'.
+[[example]]
+input = '''
+'''
+";
+
+fn fill(template: &str, day: u32) -> String {
+    template.replace("{N}", &day.to_string())
+}
+
+// Adds `line` after the last line matched by `re` whose day is lower than `day`, where
+// group 1 of `re` is the day.
+fn insert_line(text: &str, re: &Regex, day: u32, line: &str) -> Result<String, String> {
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+    let days: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(ix, l)| re.captures(l).map(|c| (ix, c[1].parse().unwrap())))
+        .collect();
+    if days.iter().any(|(_, d)| *d == day) {
+        return Err(format!("Day {day} already exists"));
+    }
+    let Some((first, _)) = days.first() else {
+        return Err(format!("No line matching '{}' to add day {day} next to", re.as_str()));
+    };
+    let at = days.iter().rfind(|(_, d)| *d < day).map_or(*first, |(ix, _)| ix + 1);
+    let indent: String = lines[days[0].0].chars().take_while(|c| c.is_whitespace()).collect();
+    lines.insert(at, format!("{indent}{line}"));
+    Ok(lines.join("\n") + "\n")
+}
+
+pub fn add_module(lib_rs: &str, day: u32) -> Result<String, String> {
+    let re = Regex::new(r"^pub mod day(\d+);$").unwrap();
+    insert_line(lib_rs, &re, day, &format!("pub mod day{day};"))
+}
+
+pub fn add_solver(registry_rs: &str, day: u32) -> Result<String, String> {
+    let re = Regex::new(r"^\s*Box::new\(day(\d+)::").unwrap();
+    insert_line(registry_rs, &re, day, &format!("Box::new(day{day}::Day{day}),"))
+}
+
+// Creates the files of `day` in the crate at `root` with its data in `data_dir`, and
+// returns their paths. Nothing is written if any of the files is already there.
+pub fn create(root: &Path, data_dir: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let read = |path: &Path| fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()));
+    let (lib_path, registry_path) = (root.join("src/lib.rs"), root.join("src/registry.rs"));
+    let edits = [
+        (lib_path.clone(), add_module(&read(&lib_path)?, day)?),
+        (registry_path.clone(), add_solver(&read(&registry_path)?, day)?),
+    ];
+    let files = [
+        (root.join(format!("src/day{day}.rs")), fill(SOLVER, day)),
+        (root.join(format!("src/bin/day{day}.rs")), fill(BINARY, day)),
+        (data_dir.join(format!("day{day}.txt")), String::new()),
+        (data_dir.join(format!("examples/day{day}.toml")), fill(FIXTURE, day)),
+    ];
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(format!("{} already exists", path.display()));
+    }
+    let mut written = vec![];
+    for (path, text) in files.into_iter().chain(edits) {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        }
+        fs::write(&path, text).map_err(|e| format!("{}: {e}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use std::env;
+
+    #[test]
+    fn test_add_lines() {
+        let lib_rs = "pub mod day1;\npub mod day2;\npub mod day10;\npub mod answers;\n";
+        assert_eq!(Ok(String::from("pub mod day1;\npub mod day2;\npub mod day3;\npub mod day10;\npub mod answers;\n")), add_module(lib_rs, 3));
+        assert_eq!(Ok(String::from("pub mod day1;\npub mod day2;\npub mod day10;\npub mod day11;\npub mod answers;\n")), add_module(lib_rs, 11));
+        assert_eq!(Err(String::from("Day 2 already exists")), add_module(lib_rs, 2));
+
+        let registry_rs = "vec![\n    Box::new(day1::Day1),\n    Box::new(day7::Day7::default()),\n]\n";
+        assert_eq!(
+            Ok(String::from("vec![\n    Box::new(day1::Day1),\n    Box::new(day7::Day7::default()),\n    Box::new(day8::Day8),\n]\n")),
+            add_solver(registry_rs, 8)
+        );
+        assert!(add_solver("vec![]\n", 8).is_err());
+    }
+
+    #[test]
+    fn test_create() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod day1;\n").unwrap();
+        fs::write(root.join("src/registry.rs"), "    Box::new(day1::Day1),\n").unwrap();
+
+        let data = root.join("data");
+        assert_eq!(6, create(&root, &data, 17).unwrap().len());
+        assert_eq!("pub mod day1;\npub mod day17;\n", fs::read_to_string(root.join("src/lib.rs")).unwrap());
+        assert!(fs::read_to_string(root.join("src/day17.rs")).unwrap().contains("const DAY: u32 = 17;"));
+        assert!(fs::read_to_string(root.join("src/bin/day17.rs")).unwrap().contains("input::from_args(17)"));
+        assert_eq!("", fs::read_to_string(data.join("day17.txt")).unwrap());
+        let fixture = examples::load(&data.join("examples/day17.toml")).unwrap();
+        assert_eq!((String::new(), None), (fixture[0].input.clone(), fixture[0].part1.clone()));
+
+        assert_eq!(Err(String::from("Day 17 already exists")), create(&root, &data, 17));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{error::ParseError, input::normalize};
+use std::{any::Any, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    Bitmap(Vec<String>),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Bitmap(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::Unsolved => write!(f, "not solved"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Answer::Bitmap(rows)
+    }
+}
+
+pub trait Solution {
+    const DAY: u32;
+    type Input: 'static;
+
+    fn parse(&self, indata: &str) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, input: &Self::Input) -> Answer;
+
+    // Puzzle parameters with their current values, set with --<name> <value>.
+    fn options(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+
+    fn set_option(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(unknown_option(name, &self.options()))
+    }
+}
+
+pub fn unknown_option(name: &str, options: &[(&'static str, String)]) -> String {
+    if options.is_empty() {
+        format!("Unknown option --{name}, this day has no options")
+    } else {
+        let names: Vec<String> = options.iter().map(|(n, _)| format!("--{n}")).collect();
+        format!("Unknown option --{name}, expected one of {}", names.join(", "))
+    }
+}
+
+pub fn option_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid value for --{name}: {value}"))
+}
+
+// Object safe version of Solution, so that all days can be kept in one registry.
+pub trait DynSolution {
+    fn day(&self) -> u32;
+    fn parse_any(&self, indata: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn part_any(&self, input: &dyn Any, part: u32) -> Answer;
+    fn options(&self) -> Vec<(&'static str, String)>;
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    fn solve(&self, indata: &str, part: u32) -> Result<Answer, ParseError> {
+        Ok(self.part_any(self.parse_any(indata)?.as_ref(), part))
+    }
+}
+
+impl<S: Solution> DynSolution for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parse_any(&self, indata: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(self.parse(&normalize(indata))?))
+    }
+
+    fn part_any(&self, input: &dyn Any, part: u32) -> Answer {
+        let input = input.downcast_ref::<S::Input>().expect("Input parsed by another day");
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Answer::Unsolved,
+        }
+    }
+
+    fn options(&self) -> Vec<(&'static str, String)> {
+        Solution::options(self)
+    }
+
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+        Solution::set_option(self, name, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Source;
+
+    struct Sum {
+        scale: i32,
+    }
+
+    impl Solution for Sum {
+        const DAY: u32 = 0;
+        type Input = Vec<i32>;
+
+        fn parse(&self, indata: &str) -> Result<Self::Input, ParseError> {
+            let src = Source::new(Self::DAY, indata);
+            src.lines().map(|l| src.number(l)).collect()
+        }
+
+        fn part1(&self, input: &Self::Input) -> Answer {
+            (self.scale * input.iter().sum::<i32>()).into()
+        }
+
+        fn part2(&self, input: &Self::Input) -> Answer {
+            input.iter().map(|n| n.to_string()).collect::<Vec<_>>().into()
+        }
+
+        fn options(&self) -> Vec<(&'static str, String)> {
+            vec![("scale", self.scale.to_string())]
+        }
+
+        fn set_option(&mut self, name: &str, value: &str) -> Result<(), String> {
+            match name {
+                "scale" => self.scale = option_value(name, value)?,
+                _ => return Err(unknown_option(name, &Solution::options(self))),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_dyn_solution() {
+        let s: &dyn DynSolution = &Sum { scale: 1 };
+        assert_eq!(Ok(Answer::Int(6)), s.solve("1\n2\n3\n", 1));
+        assert_eq!(Ok(Answer::Bitmap(vec![String::from("1"), String::from("2")])), s.solve("1\n2\n", 2));
+        assert_eq!(Ok(Answer::Unsolved), s.solve("1\n", 3));
+        assert_eq!(2, s.solve("1\nx\n", 1).unwrap_err().line);
+    }
+
+    #[test]
+    fn test_options() {
+        let mut s: Box<dyn DynSolution> = Box::new(Sum { scale: 1 });
+        s.set_option("scale", "10").unwrap();
+        assert_eq!(Ok(Answer::Int(60)), s.solve("1\n2\n3\n", 1));
+        assert_eq!(vec![("scale", String::from("10"))], s.options());
+        assert_eq!(Err(String::from("Invalid value for --scale: x")), s.set_option("scale", "x"));
+        assert_eq!(
+            Err(String::from("Unknown option --row, expected one of --scale")),
+            s.set_option("row", "1")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("42", Answer::Int(42).to_string());
+        assert_eq!("CMZ", Answer::from(String::from("CMZ")).to_string());
+        assert_eq!("#.\n.#", Answer::from(vec![String::from("#."), String::from(".#")]).to_string());
+    }
+}
@@ -0,0 +1,391 @@
+use crate::{
+    inventory::ElfStats,
+    output::{csv_row, json_array, json_fields, json_object, json_string, Format},
+};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    fmt,
+    str::FromStr,
+};
+
+// The most elves the exact search takes, any number of teams then takes at most about a
+// second on generated inventories.
+pub const EXACT_LIMIT: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Method {
+    // Exact for small inventories, otherwise the better heuristic.
+    #[default]
+    Auto,
+    Exact,
+    // Each elf, heaviest first, joins the lightest team so far.
+    Greedy,
+    // The multiway Karmarkar-Karp differencing heuristic.
+    KarmarkarKarp,
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Method::Auto),
+            "exact" => Ok(Method::Exact),
+            "greedy" => Ok(Method::Greedy),
+            "kk" => Ok(Method::KarmarkarKarp),
+            _ => Err(format!("Invalid method: {s}, expected auto, exact, greedy or kk")),
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Method::Auto => "auto",
+            Method::Exact => "exact",
+            Method::Greedy => "greedy",
+            Method::KarmarkarKarp => "kk",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// The elves of each team by number, with the team totals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Teams {
+    pub method: Method,
+    pub members: Vec<Vec<usize>>,
+    pub totals: Vec<i64>,
+}
+
+impl Teams {
+    // The teams of `elves` given the team of each, which is an index into `team_of`.
+    fn new(method: Method, elves: &[ElfStats], team_of: &[usize], k: usize) -> Self {
+        let (mut members, mut totals) = (vec![vec![]; k], vec![0; k]);
+        for (elf, team) in elves.iter().zip(team_of) {
+            members[*team].push(elf.number);
+            totals[*team] += elf.total;
+        }
+        for team in members.iter_mut() {
+            team.sort_unstable();
+        }
+        Self { method, members, totals }
+    }
+
+    pub fn imbalance(&self) -> i64 {
+        imbalance(&self.totals)
+    }
+}
+
+fn imbalance(totals: &[i64]) -> i64 {
+    totals.iter().max().unwrap_or(&0) - totals.iter().min().unwrap_or(&0)
+}
+
+// The elves by index, heaviest first.
+fn heaviest_first(elves: &[ElfStats]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..elves.len()).collect();
+    order.sort_by_key(|ix| Reverse(elves[*ix].total));
+    order
+}
+
+fn greedy(elves: &[ElfStats], k: usize) -> Vec<usize> {
+    let mut team_of = vec![0; elves.len()];
+    let mut lightest: BinaryHeap<Reverse<(i64, usize)>> = (0..k).map(|team| Reverse((0, team))).collect();
+    for ix in heaviest_first(elves) {
+        let Reverse((total, team)) = lightest.pop().unwrap();
+        team_of[ix] = team;
+        lightest.push(Reverse((total + elves[ix].total, team)));
+    }
+    team_of
+}
+
+// A partial solution of Karmarkar-Karp: k subsets of elves, sorted by total.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Partition {
+    subsets: Vec<(i64, Vec<usize>)>,
+}
+
+impl Partition {
+    fn spread(&self) -> i64 {
+        self.subsets[self.subsets.len() - 1].0 - self.subsets[0].0
+    }
+}
+
+impl Ord for Partition {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.spread().cmp(&other.spread())
+    }
+}
+
+impl PartialOrd for Partition {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Every elf starts as a partition with the elf alone in one subset. The two partitions
+// with the largest spread are merged, the heaviest subset of one with the lightest of the
+// other and so on, until one partition is left.
+fn karmarkar_karp(elves: &[ElfStats], k: usize) -> Vec<usize> {
+    let mut heap: BinaryHeap<Partition> = (0..elves.len())
+        .map(|ix| {
+            let mut subsets = vec![(0, vec![]); k];
+            subsets[k - 1] = (elves[ix].total, vec![ix]);
+            Partition { subsets }
+        })
+        .collect();
+    while heap.len() > 1 {
+        let (a, b) = (heap.pop().unwrap(), heap.pop().unwrap());
+        let mut subsets: Vec<(i64, Vec<usize>)> = a
+            .subsets
+            .into_iter()
+            .zip(b.subsets.into_iter().rev())
+            .map(|((ta, mut ma), (tb, mb))| {
+                ma.extend(mb);
+                (ta + tb, ma)
+            })
+            .collect();
+        subsets.sort_by_key(|s| s.0);
+        heap.push(Partition { subsets });
+    }
+    let mut team_of = vec![0; elves.len()];
+    for (team, (_, members)) in heap.pop().map(|p| p.subsets).unwrap_or_default().into_iter().enumerate() {
+        for ix in members {
+            team_of[ix] = team;
+        }
+    }
+    team_of
+}
+
+// Branch and bound over the team of each elf, heaviest first, starting from the best of
+// the heuristics. Teams with the same total are interchangeable so only the first of them
+// is tried.
+struct Search {
+    weights: Vec<i64>,
+    // The total of the elves from each position on.
+    remaining: Vec<i64>,
+    // The most the smallest team can end with and the least the largest one can.
+    target: (i64, i64),
+    totals: Vec<i64>,
+    assigned: Vec<usize>,
+    best: (i64, Vec<usize>),
+}
+
+impl Search {
+    // Whether the teams can still end up closer than the best split so far. The smallest
+    // team ends at most at the average and the largest at least at it, so to do better
+    // every team has to end within the best imbalance of the average.
+    fn promising(&self, pos: usize) -> bool {
+        let (low, high) = (self.target.1 - self.best.0 + 1, self.target.0 + self.best.0 - 1);
+        let need: i64 = self.totals.iter().map(|t| (low - t).max(0)).sum();
+        let room: i64 = self.totals.iter().map(|t| (high - t).max(0)).sum();
+        self.totals.iter().all(|t| *t <= high) && need <= self.remaining[pos] && room >= self.remaining[pos]
+    }
+
+    fn run(&mut self, pos: usize) {
+        if self.best.0 <= self.target.1 - self.target.0 || !self.promising(pos) {
+            return;
+        }
+        if pos == self.weights.len() {
+            let imbalance = imbalance(&self.totals);
+            if imbalance < self.best.0 {
+                self.best = (imbalance, self.assigned.clone());
+            }
+            return;
+        }
+        // Lighter teams first, they lead to good splits sooner.
+        let mut teams: Vec<usize> = (0..self.totals.len()).collect();
+        teams.sort_by_key(|team| self.totals[*team]);
+        teams.dedup_by_key(|team| self.totals[*team]);
+        for team in teams {
+            self.totals[team] += self.weights[pos];
+            self.assigned[pos] = team;
+            self.run(pos + 1);
+            self.totals[team] -= self.weights[pos];
+        }
+    }
+}
+
+fn exact(elves: &[ElfStats], k: usize) -> Vec<usize> {
+    let start = [greedy(elves, k), karmarkar_karp(elves, k)]
+        .into_iter()
+        .map(|team_of| (Teams::new(Method::Exact, elves, &team_of, k).imbalance(), team_of))
+        .min_by_key(|(imbalance, _)| *imbalance)
+        .unwrap();
+    let order = heaviest_first(elves);
+    let weights: Vec<i64> = order.iter().map(|ix| elves[*ix].total).collect();
+    let mut remaining = vec![0; weights.len() + 1];
+    for pos in (0..weights.len()).rev() {
+        remaining[pos] = remaining[pos + 1] + weights[pos];
+    }
+    // The smallest team ends at most at the average, empty with more teams than elves and
+    // at most at the lightest elf with as many. The largest ends at least at the average
+    // and at the heaviest elf.
+    let (total, k) = (remaining[0], k as i64);
+    let smallest = match elves.len().cmp(&(k as usize)) {
+        Ordering::Less => 0,
+        Ordering::Equal => weights.last().copied().unwrap_or(0).min(total.div_euclid(k)),
+        Ordering::Greater => total.div_euclid(k),
+    };
+    let largest = (total.div_euclid(k) + (total.rem_euclid(k) != 0) as i64).max(weights.first().copied().unwrap_or(0));
+    let target = (smallest, largest);
+    let best = (start.0, order.iter().map(|ix| start.1[*ix]).collect());
+    let mut search = Search { weights, remaining, target, totals: vec![0; k as usize], assigned: vec![0; elves.len()], best };
+    search.run(0);
+    let mut team_of = vec![0; elves.len()];
+    for (pos, ix) in order.iter().enumerate() {
+        team_of[*ix] = search.best.1[pos];
+    }
+    team_of
+}
+
+// Splits `elves` into `k` teams, some of them empty when there are fewer elves than teams.
+pub fn split(elves: &[ElfStats], k: usize, method: Method) -> Result<Teams, String> {
+    if k == 0 {
+        return Err(String::from("There must be at least one team"));
+    }
+    // Bounds every team total and every difference of them.
+    if elves.iter().try_fold(0i64, |sum, e| sum.checked_add(e.total.checked_abs()?)).is_none() {
+        return Err(String::from("Total calories too large"));
+    }
+    let method = match method {
+        Method::Auto if elves.len() <= EXACT_LIMIT => Method::Exact,
+        Method::Auto => {
+            let (a, b) = (Teams::new(Method::Greedy, elves, &greedy(elves, k), k), Teams::new(Method::KarmarkarKarp, elves, &karmarkar_karp(elves, k), k));
+            return Ok(if b.imbalance() < a.imbalance() { b } else { a });
+        }
+        Method::Exact if elves.len() > EXACT_LIMIT => {
+            return Err(format!("The exact search takes at most {EXACT_LIMIT} elves, not {}, use greedy or kk", elves.len()));
+        }
+        Method::Exact if elves.iter().any(|e| e.total < 0) => return Err(String::from("The exact search needs totals of at least 0")),
+        method => method,
+    };
+    let team_of = match method {
+        Method::Greedy => greedy(elves, k),
+        Method::KarmarkarKarp => karmarkar_karp(elves, k),
+        _ => exact(elves, k),
+    };
+    Ok(Teams::new(method, elves, &team_of, k))
+}
+
+// A line per team as text and json, or the team of each elf as csv. Teams are numbered
+// from 1.
+pub fn format_teams(format: Format, teams: &Teams) -> Vec<String> {
+    let numbers = |members: &[usize]| members.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(", ");
+    match format {
+        Format::Text => {
+            let mut res: Vec<String> = teams
+                .members
+                .iter()
+                .zip(&teams.totals)
+                .enumerate()
+                .map(|(ix, (members, total))| format!("Team {}: {total} calories, {} elves: {}", ix + 1, members.len(), numbers(members)))
+                .collect();
+            res.push(format!("Imbalance: {} ({})", teams.imbalance(), teams.method));
+            res
+        }
+        Format::Json => {
+            let team = |(members, total): (&Vec<usize>, &i64)| {
+                json_object(&[("total", total.to_string()), ("elves", json_array(members.iter().map(|n| n.to_string())))])
+            };
+            json_fields(&[
+                ("method", json_string(&teams.method.to_string())),
+                ("imbalance", teams.imbalance().to_string()),
+                ("teams", json_array(teams.members.iter().zip(&teams.totals).map(team))),
+            ])
+        }
+        Format::Csv => {
+            let mut rows: Vec<(usize, usize)> = teams.members.iter().enumerate().flat_map(|(team, m)| m.iter().map(move |n| (*n, team + 1))).collect();
+            rows.sort_unstable();
+            let mut res = vec![csv_row(&["elf", "team"])];
+            res.extend(rows.iter().map(|(elf, team)| csv_row(&[elf.to_string(), team.to_string()])));
+            res
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    fn elves(totals: &[i64]) -> Vec<ElfStats> {
+        totals.iter().enumerate().map(|(ix, total)| ElfStats { number: ix + 1, items: 1, total: *total }).collect()
+    }
+
+    // The smallest imbalance of any split, trying them all.
+    fn brute_force(totals: &[i64], k: usize) -> i64 {
+        let mut best = i64::MAX;
+        for code in 0..k.pow(totals.len() as u32) {
+            let mut sums = vec![0; k];
+            let mut c = code;
+            for t in totals {
+                sums[c % k] += t;
+                c /= k;
+            }
+            best = best.min(imbalance(&sums));
+        }
+        best
+    }
+
+    #[test]
+    fn test_split() {
+        let example = elves(&[6000, 4000, 11000, 24000, 10000]);
+        let teams = split(&example, 2, Method::Exact).unwrap();
+        assert_eq!(vec![vec![2, 4], vec![1, 3, 5]], teams.members);
+        assert_eq!(vec![28000, 27000], teams.totals);
+        assert_eq!(1000, teams.imbalance());
+        assert_eq!(9000, split(&example, 3, Method::Auto).unwrap().imbalance());
+
+        // Greedy misses the perfect split of these, differencing gets closer.
+        let tricky = elves(&[8, 7, 6, 5, 4]);
+        assert_eq!(4, split(&tricky, 2, Method::Greedy).unwrap().imbalance());
+        assert_eq!(2, split(&tricky, 2, Method::KarmarkarKarp).unwrap().imbalance());
+        assert_eq!(0, split(&tricky, 2, Method::Exact).unwrap().imbalance());
+
+        let teams = split(&elves(&[5, 3]), 3, Method::Auto).unwrap();
+        assert_eq!(2, teams.members.iter().filter(|m| !m.is_empty()).count());
+        assert_eq!(5, teams.imbalance());
+        assert!(split(&tricky, 0, Method::Auto).is_err());
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(Err(String::from("Total calories too large")), split(&elves(&[i64::MAX, 1]), 2, Method::Greedy));
+        assert_eq!(Err(String::from("Total calories too large")), split(&elves(&[i64::MIN]), 2, Method::Greedy));
+        let many = elves(&[1; EXACT_LIMIT + 1]);
+        assert!(split(&many, 3, Method::Exact).unwrap_err().starts_with("The exact search takes at most 16 elves"));
+        assert_ne!(Method::Exact, split(&many, 3, Method::Auto).unwrap().method);
+        assert!(split(&elves(&[3, -1, 2]), 2, Method::Exact).is_err());
+        assert_eq!(2, split(&elves(&[3, -1, 2]), 2, Method::Greedy).unwrap().imbalance());
+    }
+
+    #[test]
+    fn test_exact_is_optimal() {
+        let mut rng = Rng::new(7);
+        for _ in 0..30 {
+            let n = rng.range(1..=7) as usize;
+            let totals: Vec<i64> = (0..n).map(|_| rng.range(1..=50)).collect();
+            for k in 2..=5 {
+                let teams = split(&elves(&totals), k, Method::Exact).unwrap();
+                assert_eq!(brute_force(&totals, k), teams.imbalance(), "{totals:?} in {k} teams");
+                assert_eq!(totals.iter().sum::<i64>(), teams.totals.iter().sum::<i64>());
+                assert_eq!(n, teams.members.iter().map(|m| m.len()).sum::<usize>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let teams = Teams { method: Method::Exact, members: vec![vec![3, 4, 5], vec![1, 2]], totals: vec![15, 15] };
+        assert_eq!(
+            vec!["Team 1: 15 calories, 3 elves: 3, 4, 5", "Team 2: 15 calories, 2 elves: 1, 2", "Imbalance: 0 (exact)"],
+            format_teams(Format::Text, &teams)
+        );
+        assert_eq!(vec!["elf,team", "1,2", "2,2", "3,1", "4,1", "5,1"], format_teams(Format::Csv, &teams));
+        let json = format_teams(Format::Json, &teams).join("\n");
+        assert!(json.contains("\"imbalance\": 0,"));
+        assert!(json.contains("{\"total\": 15, \"elves\": [1, 2]}"));
+    }
+}
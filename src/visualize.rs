@@ -0,0 +1,286 @@
+use crate::{
+    grid::Grid,
+    input,
+    point::{Bounds2, Point2},
+};
+use std::{
+    collections::HashMap,
+    env,
+    io::{self, Read, Write},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+pub type Pos = Point2<i32>;
+
+pub trait Scene {
+    // Advances the simulation one step, false once there is nothing left to do.
+    fn step(&mut self) -> bool;
+
+    fn draw(&self, canvas: &mut Canvas);
+
+    // A line shown below the picture.
+    fn status(&self) -> String {
+        String::new()
+    }
+
+    // The steps per frame when not given on the command line, long simulations need more.
+    fn steps_per_frame(&self) -> usize {
+        1
+    }
+}
+
+// The characters of a frame by position, x to the right and y down. The viewport follows
+// the focus when the picture is larger than the terminal.
+#[derive(Debug, Clone, Default)]
+pub struct Canvas {
+    cells: HashMap<Pos, char>,
+    focus: Option<Pos>,
+}
+
+impl Canvas {
+    pub fn set(&mut self, p: Pos, ch: char) {
+        self.cells.insert(p, ch);
+    }
+
+    pub fn focus(&mut self, p: Pos) {
+        self.focus = Some(p);
+    }
+
+    // Fills a rectangle from `rows`, with its top left corner at `at`.
+    pub fn text(&mut self, at: Pos, rows: &[String]) {
+        for (y, row) in rows.iter().enumerate() {
+            for (x, ch) in row.chars().enumerate() {
+                self.set(at + Pos::new(x as i32, y as i32), ch);
+            }
+        }
+    }
+
+    // The top left corner of the `width` by `height` viewport, centered on the focus.
+    fn viewport(&self, width: i32, height: i32) -> Option<(Bounds2<i32>, Pos)> {
+        let bounds = Bounds2::from_points(self.cells.keys().copied())?;
+        let focus = self.focus.unwrap_or(bounds.min);
+        let start = |min: i32, max: i32, focus: i32, size: i32| match max - min < size {
+            true => min,
+            false => (focus - size / 2).clamp(min, max - size + 1),
+        };
+        let corner = Pos::new(start(bounds.min.x, bounds.max.x, focus.x, width), start(bounds.min.y, bounds.max.y, focus.y, height));
+        Some((bounds, corner))
+    }
+
+    fn get(&self, x: i32, y: i32) -> char {
+        self.cells.get(&Pos::new(x, y)).copied().unwrap_or(' ')
+    }
+
+    // The part of the picture that fits in `width` by `height`, centered on the focus.
+    pub fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let (width, height) = (width.max(1) as i32, height.max(1) as i32);
+        let Some((bounds, corner)) = self.viewport(width, height) else {
+            return vec![];
+        };
+        (corner.y..=bounds.max.y.min(corner.y + height - 1))
+            .map(|y| {
+                let row: String = (corner.x..=bounds.max.x.min(corner.x + width - 1)).map(|x| self.get(x, y)).collect();
+                String::from(row.trim_end())
+            })
+            .collect()
+    }
+
+    // As render, but always `width` by `height` with blanks around a smaller picture.
+    pub fn window(&self, width: usize, height: usize) -> Grid<char> {
+        let mut res = Grid::new(height, width, ' ');
+        if let Some((_, corner)) = self.viewport(width as i32, height as i32) {
+            for (row, col) in res.positions() {
+                res[(row, col)] = self.get(corner.x + col as i32, corner.y + row as i32);
+            }
+        }
+        res
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub fps: f64,
+    pub steps: Option<usize>,
+    pub paused: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { fps: 20.0, steps: None, paused: false }
+    }
+}
+
+// Takes the visualization flags out of the arguments of a day binary. The settings are
+// there only with --visualize.
+pub fn split_args(args: &[String]) -> Result<(Option<Settings>, Vec<String>), String> {
+    let mut settings = Settings::default();
+    let mut visualize = false;
+    let mut rest = vec![];
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = |name: &str| it.next().ok_or(format!("{name} needs a value")).cloned();
+        match arg.as_str() {
+            "--visualize" => visualize = true,
+            "--paused" => settings.paused = true,
+            "--fps" => {
+                let fps = value("--fps")?;
+                settings.fps = fps.parse().ok().filter(|f| *f > 0.0).ok_or(format!("Invalid frame rate: {fps}"))?;
+            }
+            "--steps" => {
+                let steps = value("--steps")?;
+                settings.steps = Some(steps.parse().ok().filter(|s| *s > 0).ok_or(format!("Invalid steps: {steps}"))?);
+            }
+            _ => rest.push(arg.clone()),
+        }
+    }
+    if !visualize && (settings != Settings::default()) {
+        return Err(String::from("--fps, --steps and --paused need --visualize"));
+    }
+    Ok((visualize.then_some(settings), rest))
+}
+
+// Reads the input for a day binary as input::from_args does, along with the settings
+// when it is to be animated.
+pub fn from_args(day: u32) -> (String, Option<Settings>) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (settings, args) = input::or_exit(split_args(&args));
+    (input::or_exit(input::read_args(day, &args)), settings)
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Puts the terminal in a mode where keys are read as they are pressed without waiting,
+// and back when dropped. Without a terminal there are no keys.
+struct Terminal {
+    saved: Option<String>,
+}
+
+impl Terminal {
+    fn new() -> Self {
+        let saved = stty(&["-g"]).map(|s| String::from(s.trim()));
+        if saved.is_some() {
+            stty(&["-icanon", "-echo", "min", "0", "time", "0"]);
+        }
+        print!("\x1b[?25l\x1b[2J");
+        Self { saved }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        let size = stty(&["size"]).and_then(|s| s.split_once(' ').and_then(|(r, c)| Some((c.trim().parse().ok()?, r.parse().ok()?))));
+        size.unwrap_or((80, 24))
+    }
+
+    fn keys(&self) -> Vec<char> {
+        let mut buf = [0; 16];
+        match self.saved {
+            Some(_) => io::stdin().read(&mut buf).map_or(vec![], |n| buf[..n].iter().map(|b| *b as char).collect()),
+            None => vec![],
+        }
+    }
+
+    fn show(&self, lines: &[String]) {
+        let mut out = io::stdout().lock();
+        let _ = write!(out, "\x1b[H");
+        for line in lines {
+            let _ = write!(out, "{line}\x1b[K\r\n");
+        }
+        let _ = write!(out, "\x1b[J");
+        let _ = out.flush();
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        if let Some(saved) = &self.saved {
+            stty(&[saved]);
+        }
+        print!("\x1b[?25h");
+        let _ = io::stdout().flush();
+    }
+}
+
+// Runs `scene` to its end, or until q is pressed, drawing it as it goes. Space pauses,
+// n steps while paused and + and - change the frame rate.
+pub fn animate(scene: &mut dyn Scene, settings: &Settings) {
+    let terminal = Terminal::new();
+    let steps = settings.steps.unwrap_or_else(|| scene.steps_per_frame());
+    let (mut fps, mut paused, mut running) = (settings.fps, settings.paused, true);
+    loop {
+        let (width, height) = terminal.size();
+        let mut canvas = Canvas::default();
+        scene.draw(&mut canvas);
+        let mut lines = canvas.render(width, height.saturating_sub(2));
+        let state = match (running, paused) {
+            (false, _) => "done",
+            (true, true) => "paused, n to step",
+            (true, false) => "space to pause",
+        };
+        lines.push(format!("{} [{state}, {fps} fps, +/- to change, q to quit]", scene.status()));
+        terminal.show(&lines);
+        if !running {
+            return;
+        }
+
+        let deadline = Instant::now() + Duration::from_secs_f64(1.0 / fps);
+        let mut advance = false;
+        while !advance && (paused || Instant::now() < deadline) {
+            for key in terminal.keys() {
+                match key {
+                    ' ' => paused = !paused,
+                    'n' | '.' if paused => advance = true,
+                    '+' => fps = (fps * 2.0).min(1000.0),
+                    '-' => fps = (fps / 2.0).max(0.25),
+                    'q' => return,
+                    _ => {}
+                }
+            }
+            thread::sleep(Duration::from_millis(10));
+            advance |= !paused && Instant::now() >= deadline;
+        }
+        for _ in 0..steps {
+            if !scene.step() {
+                running = false;
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let mut canvas = Canvas::default();
+        canvas.text(Pos::new(-2, 0), &[String::from("abcdef"), String::from("gh"), String::from("ijklmn")]);
+        assert_eq!(vec!["abcdef", "gh", "ijklmn"], canvas.render(10, 10));
+        assert_eq!(vec!["abc", "gh"], canvas.render(3, 2));
+
+        canvas.focus(Pos::new(2, 2));
+        assert_eq!(vec!["de", "", "lm"], canvas.render(2, 3));
+        canvas.focus(Pos::new(10, 10));
+        assert_eq!(vec!["", "mn"], canvas.render(2, 2));
+        assert!(Canvas::default().render(10, 10).is_empty());
+        assert_eq!(vec!["    ", "klmn"], canvas.window(4, 2).render(|c| *c));
+        assert_eq!(vec!["  "], Canvas::default().window(2, 1).render(|c| *c));
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(Ok((None, args("--input x"))), split_args(&args("--input x")));
+        let settings = Settings { fps: 5.0, steps: Some(3), paused: false };
+        assert_eq!(Ok((Some(settings), args("--input x"))), split_args(&args("--visualize --fps 5 --input x --steps 3")));
+        assert!(split_args(&args("--visualize --fps 0")).is_err());
+        assert!(split_args(&args("--paused")).is_err());
+    }
+}
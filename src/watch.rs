@@ -0,0 +1,388 @@
+use crate::{
+    answers::{Answers, Verdict},
+    solution::Answer,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// How long to wait for more changes after one, an editor saving a file can touch it
+// several times.
+const SETTLE: Duration = Duration::from_millis(200);
+
+// The files a day depends on: everything under src, its input and its examples.
+#[derive(Debug, Clone)]
+pub struct Watched {
+    pub src: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+impl Watched {
+    pub fn new(root: &Path, data_dir: &Path, day: u32) -> Self {
+        Self {
+            src: root.join("src"),
+            files: vec![data_dir.join(format!("day{day}.txt")), data_dir.join(format!("examples/day{day}.toml"))],
+        }
+    }
+
+    pub fn is_relevant(&self, path: &Path) -> bool {
+        (path.starts_with(&self.src) && path.extension().is_some_and(|e| e == "rs")) || self.files.iter().any(|f| f == path)
+    }
+
+    // The directories to watch, the ones holding the files so that files replaced by a
+    // rename are noticed too.
+    fn dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![];
+        let mut to_visit = vec![self.src.clone()];
+        while let Some(dir) = to_visit.pop() {
+            if let Ok(entries) = fs::read_dir(&dir) {
+                to_visit.extend(entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()));
+            }
+            dirs.push(dir);
+        }
+        for file in &self.files {
+            if let Some(dir) = file.parent().filter(|d| !dirs.iter().any(|p| p == d)) {
+                dirs.push(dir.to_path_buf());
+            }
+        }
+        dirs
+    }
+}
+
+pub trait Watcher {
+    // Blocks until relevant files change and returns them.
+    fn wait(&mut self) -> Result<Vec<PathBuf>, String>;
+}
+
+pub fn watcher(watched: Watched) -> Result<Box<dyn Watcher>, String> {
+    #[cfg(target_os = "linux")]
+    return Ok(Box::new(inotify::Inotify::new(watched)?));
+    #[cfg(not(target_os = "linux"))]
+    return Ok(Box::new(Poll::new(watched)));
+}
+
+// Compares modification times twice a second.
+pub struct Poll {
+    watched: Watched,
+    seen: HashMap<PathBuf, SystemTime>,
+}
+
+impl Poll {
+    pub fn new(watched: Watched) -> Self {
+        let seen = Self::scan(&watched);
+        Self { watched, seen }
+    }
+
+    fn scan(watched: &Watched) -> HashMap<PathBuf, SystemTime> {
+        let mut res = HashMap::new();
+        for dir in watched.dirs() {
+            for path in fs::read_dir(&dir).into_iter().flatten().flatten().map(|e| e.path()) {
+                if let Some(modified) = watched.is_relevant(&path).then(|| fs::metadata(&path).and_then(|m| m.modified()).ok()).flatten() {
+                    res.insert(path, modified);
+                }
+            }
+        }
+        res
+    }
+}
+
+impl Watcher for Poll {
+    fn wait(&mut self) -> Result<Vec<PathBuf>, String> {
+        loop {
+            thread::sleep(Duration::from_millis(500));
+            let now = Self::scan(&self.watched);
+            let mut changed: Vec<PathBuf> = now.iter().filter(|(p, t)| self.seen.get(*p) != Some(t)).map(|(p, _)| p.clone()).collect();
+            changed.extend(self.seen.keys().filter(|p| !now.contains_key(*p)).cloned());
+            self.seen = now;
+            if !changed.is_empty() {
+                changed.sort();
+                return Ok(changed);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use super::{Watched, Watcher, SETTLE};
+    use std::{
+        ffi::CString,
+        fs::File,
+        io::{self, Read},
+        os::{fd::FromRawFd, unix::ffi::OsStrExt},
+        path::PathBuf,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    const IN_NONBLOCK: i32 = 0o4000;
+    const IN_CLOEXEC: i32 = 0o2000000;
+    const IN_CLOSE_WRITE: u32 = 0x8;
+    const IN_MOVED_TO: u32 = 0x80;
+    const IN_DELETE: u32 = 0x200;
+
+    extern "C" {
+        fn inotify_init1(flags: i32) -> i32;
+        fn inotify_add_watch(fd: i32, path: *const std::ffi::c_char, mask: u32) -> i32;
+    }
+
+    pub struct Inotify {
+        watched: Watched,
+        file: File,
+        dirs: Vec<(i32, PathBuf)>,
+    }
+
+    impl Inotify {
+        pub fn new(watched: Watched) -> Result<Self, String> {
+            // SAFETY: plain system calls, the descriptor is owned by `file` from here on.
+            let fd = unsafe { inotify_init1(IN_NONBLOCK | IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(format!("inotify: {}", io::Error::last_os_error()));
+            }
+            let file = unsafe { File::from_raw_fd(fd) };
+            let mut dirs = vec![];
+            for dir in watched.dirs() {
+                let path = CString::new(dir.as_os_str().as_bytes()).map_err(|e| e.to_string())?;
+                // SAFETY: `path` is a valid C string that outlives the call.
+                let wd = unsafe { inotify_add_watch(fd, path.as_ptr(), IN_CLOSE_WRITE | IN_MOVED_TO | IN_DELETE) };
+                if wd >= 0 {
+                    dirs.push((wd, dir));
+                }
+            }
+            Ok(Self { watched, file, dirs })
+        }
+
+        // The paths in the events in `buf`, laid out as struct inotify_event.
+        fn paths(&self, mut buf: &[u8]) -> Vec<PathBuf> {
+            let mut res = vec![];
+            while buf.len() >= 16 {
+                let wd = i32::from_ne_bytes(buf[0..4].try_into().unwrap());
+                let len = u32::from_ne_bytes(buf[12..16].try_into().unwrap()) as usize;
+                let name: Vec<u8> = buf[16..16 + len].iter().copied().take_while(|b| *b != 0).collect();
+                if let Some((_, dir)) = self.dirs.iter().find(|(w, _)| *w == wd) {
+                    res.push(dir.join(std::ffi::OsStr::from_bytes(&name)));
+                }
+                buf = &buf[16 + len..];
+            }
+            res
+        }
+    }
+
+    impl Watcher for Inotify {
+        fn wait(&mut self) -> Result<Vec<PathBuf>, String> {
+            let mut changed: Vec<PathBuf> = vec![];
+            let mut last_change = Instant::now();
+            let mut buf = [0; 4096];
+            loop {
+                match self.file.read(&mut buf) {
+                    Ok(n) => {
+                        for path in self.paths(&buf[..n]) {
+                            if self.watched.is_relevant(&path) && !changed.contains(&path) {
+                                changed.push(path);
+                                last_change = Instant::now();
+                            }
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        if !changed.is_empty() && last_change.elapsed() >= SETTLE {
+                            changed.sort();
+                            return Ok(changed);
+                        }
+                        thread::sleep(Duration::from_millis(50));
+                    }
+                    Err(e) => return Err(format!("inotify: {e}")),
+                }
+            }
+        }
+    }
+}
+
+// The outcome of one round of building, testing and solving.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    // The first lines of the compiler errors, if the build failed.
+    pub build_errors: Option<Vec<String>>,
+    pub passed: usize,
+    pub failed: Vec<String>,
+    pub answers: Vec<(u32, String)>,
+    // Why there are no answers.
+    pub run_error: Option<String>,
+}
+
+// Adds up the "test result" lines of cargo test and collects the failed tests.
+pub fn parse_test_output(output: &str) -> (usize, Vec<String>) {
+    let mut passed = 0;
+    let mut failed = vec![];
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("test result: ") {
+            passed += rest
+                .split(';')
+                .find_map(|s| s.trim().trim_start_matches("ok. ").trim_start_matches("FAILED. ").strip_suffix(" passed"))
+                .and_then(|n| n.parse::<usize>().ok())
+                .unwrap_or(0);
+        } else if let Some(name) = line.strip_prefix("test ").and_then(|l| l.strip_suffix(" ... FAILED")) {
+            failed.push(String::from(name));
+        }
+    }
+    (passed, failed)
+}
+
+// The answers in the text output of `aoc run`, a picture continues on the lines after
+// its part.
+pub fn parse_answers(output: &str) -> Vec<(u32, String)> {
+    let mut res: Vec<(u32, String)> = vec![];
+    for line in output.lines() {
+        let part = line.strip_prefix("Part").and_then(|l| l.split_once(':')).and_then(|(p, a)| Some((p.parse().ok()?, a)));
+        match (part, res.last_mut()) {
+            (Some((part, answer)), _) => res.push((part, String::from(answer.trim()))),
+            (None, Some((_, answer))) if !line.starts_with("Day ") => {
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(line);
+            }
+            _ => {}
+        }
+    }
+    res
+}
+
+fn to_answer(text: &str) -> Answer {
+    match text {
+        "not solved" => Answer::Unsolved,
+        t if t.contains('\n') => Answer::Bitmap(t.lines().map(String::from).collect()),
+        t => t.parse().map_or_else(|_| Answer::Text(String::from(t)), Answer::Int),
+    }
+}
+
+// A few lines on how the round went, comparing the answers with the previous round and
+// with the recorded ones.
+pub fn summary(day: u32, report: &Report, previous: Option<&Report>, answers: &Answers) -> Vec<String> {
+    if let Some(errors) = &report.build_errors {
+        let mut res = vec![String::from("Build failed")];
+        res.extend(errors.iter().cloned());
+        return res;
+    }
+    let mut res = vec![match report.failed.len() {
+        0 => format!("Tests: {} passed", report.passed),
+        n => format!("Tests: {} passed, {n} FAILED: {}", report.passed, report.failed.join(", ")),
+    }];
+    if let Some(e) = &report.run_error {
+        res.push(format!("Run failed: {e}"));
+    }
+    for (part, answer) in &report.answers {
+        let before = previous.and_then(|p| p.answers.iter().find(|(q, _)| q == part)).map(|(_, a)| a);
+        let change = match before {
+            Some(b) if b != answer => format!(" (was {})", b.replace('\n', " ")),
+            _ => String::new(),
+        };
+        let verdict = match answers.check(day, *part, to_answer(answer)) {
+            Verdict::Correct => ", correct",
+            Verdict::Wrong { actual: Answer::Unsolved, .. } | Verdict::Unknown(_) => "",
+            Verdict::Wrong { .. } => ", WRONG",
+        };
+        let shown = if answer.contains('\n') { format!("\n{answer}") } else { answer.clone() };
+        res.push(format!("Part{part}: {shown}{change}{verdict}"));
+    }
+    res
+}
+
+fn cargo(root: &Path, args: &[&str]) -> Result<(bool, String, String), String> {
+    let output = Command::new(std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo")))
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|e| format!("cargo: {e}"))?;
+    Ok((
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    ))
+}
+
+// Builds the crate at `root`, runs the tests of the day and the example fixtures and
+// then the day on its input.
+pub fn run_round(root: &Path, day: u32, release: bool) -> Result<Report, String> {
+    let profile: &[&str] = if release { &["--release"] } else { &[] };
+    let (ok, _, stderr) = cargo(root, &[&["build", "--quiet"], profile].concat())?;
+    if !ok {
+        let errors = stderr.lines().filter(|l| !l.trim().is_empty()).take(12).map(String::from).collect();
+        return Ok(Report { build_errors: Some(errors), ..Report::default() });
+    }
+    let filter = format!("day{day}::");
+    let (_, stdout, _) = cargo(root, &[&["test", "--quiet", "--lib"], profile, &["--", &filter, "examples::"]].concat())?;
+    let (passed, failed) = parse_test_output(&stdout);
+    let day_arg = day.to_string();
+    let (ok, stdout, stderr) = cargo(root, &[&["run", "--quiet", "--bin", "aoc"], profile, &["--", "run", &day_arg]].concat())?;
+    let run_error = (!ok).then(|| String::from(stderr.lines().last().unwrap_or("unknown error")));
+    Ok(Report { build_errors: None, passed, failed, answers: parse_answers(&stdout), run_error })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_parse_output() {
+        let output = "running 3 tests\ntest day1::tests::a ... ok\ntest day1::tests::b ... FAILED\n\
+                      test result: FAILED. 2 passed; 1 failed; 0 ignored\n\ntest result: ok. 4 passed; 0 failed\n";
+        assert_eq!((6, vec![String::from("day1::tests::b")]), parse_test_output(output));
+
+        let answers = parse_answers("Day 10\nPart1: 13140\nPart2:\n##..\n#..#\n");
+        assert_eq!(vec![(1, String::from("13140")), (2, String::from("##..\n#..#"))], answers);
+    }
+
+    #[test]
+    fn test_summary() {
+        let answers = Answers::parse("[day3]\npart1 = 157\npart2 = 70\n").unwrap();
+        let first = Report { passed: 3, answers: vec![(1, String::from("150")), (2, String::from("70"))], ..Report::default() };
+        let second = Report {
+            passed: 2,
+            failed: vec![String::from("day3::tests::b")],
+            answers: vec![(1, String::from("157")), (2, String::from("not solved"))],
+            ..Report::default()
+        };
+        assert_eq!(vec!["Tests: 3 passed", "Part1: 150, WRONG", "Part2: 70, correct"], summary(3, &first, None, &answers));
+        assert_eq!(
+            vec!["Tests: 2 passed, 1 FAILED: day3::tests::b", "Part1: 157 (was 150), correct", "Part2: not solved (was 70)"],
+            summary(3, &second, Some(&first), &answers)
+        );
+        let broken = Report { build_errors: Some(vec![String::from("error[E0425]")]), ..Report::default() };
+        assert_eq!(vec!["Build failed", "error[E0425]"], summary(3, &broken, Some(&first), &answers));
+    }
+
+    #[test]
+    fn test_watcher() {
+        let root = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::create_dir_all(root.join("data")).unwrap();
+        let watched = Watched::new(&root, &root.join("data"), 3);
+        assert!(watched.is_relevant(&root.join("src/bin/day3.rs")));
+        assert!(!watched.is_relevant(&root.join("data/day4.txt")));
+
+        let mut w = watcher(watched).unwrap();
+        let writer = {
+            let root = root.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(100));
+                fs::write(root.join("data/day4.txt"), "ignored").unwrap();
+                fs::write(root.join("src/bin/day3.rs"), "fn main() {}").unwrap();
+                fs::write(root.join("data/day3.txt"), "1\n").unwrap();
+            })
+        };
+        let mut changed = w.wait().unwrap();
+        writer.join().unwrap();
+        if changed.len() < 2 {
+            changed.extend(w.wait().unwrap());
+        }
+        assert_eq!(vec![root.join("data/day3.txt"), root.join("src/bin/day3.rs")], changed);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}